@@ -1,31 +1,123 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 
+use futures::stream::{FuturesUnordered, StreamExt};
+use tokio::sync::Semaphore;
 use tracing::{debug, info, instrument, warn};
 
 use super::error::ApiError;
 use super::types::{Comment, Feed, HnItem, Story};
-use crate::storage::{StorableComment, StorableStory, Storage};
+use crate::storage::{Cache, StorableComment, StorableSearchHit, StorableStory};
 
 const API_BASE: &str = "https://hacker-news.firebaseio.com/v0";
 const PAGE_SIZE: usize = 30;
 
+/// Default cap on simultaneous requests against the Firebase API, so a
+/// popular thread with thousands of comments doesn't fire them all at
+/// once. Override per-client with [`HnClient::with_max_concurrency`].
+const DEFAULT_MAX_CONCURRENCY: usize = 16;
+
+/// How many times a retryable error (429, 5xx, timeout) is retried before
+/// the item is given up on.
+const MAX_RETRIES: u32 = 3;
+
+/// Base delay for exponential backoff between retries; doubled per attempt
+/// and padded with a few tens of milliseconds of jitter so a burst of
+/// simultaneously-failing requests doesn't retry in lockstep.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(200);
+
+/// Which cached table a [`SearchHit`] matched in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchHitKind {
+    Story,
+    Comment,
+}
+
+/// A single FTS5 match over cached story titles or comment bodies, ranked
+/// by BM25 (lower `score` is more relevant, matching SQLite's `bm25()`).
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub kind: SearchHitKind,
+    /// The story or comment id that matched.
+    pub id: u64,
+    /// The story this hit belongs to (itself, for a story hit), so the UI
+    /// can jump straight to the right comment thread.
+    pub story_id: u64,
+    /// Matched text with `<b>...</b>` highlights around the query terms.
+    pub snippet: String,
+    pub score: f64,
+}
+
+/// Raw response from `{API_BASE}/updates.json`: ids of items and user
+/// profiles that changed recently. Profile updates aren't actionable here
+/// (we don't cache user profiles), so only `items` is used.
+#[derive(Debug, serde::Deserialize)]
+struct UpdatesResponse {
+    items: Vec<u64>,
+    #[allow(dead_code)]
+    profiles: Vec<String>,
+}
+
+/// Outcome of [`HnClient::sync_updates`]: which cached stories changed and
+/// got refetched, plus how many new replies each one picked up, so the UI
+/// can show "N new replies" per story without re-walking the tree itself to
+/// count them.
+#[derive(Debug, Clone, Default)]
+pub struct SyncResult {
+    pub updated_stories: Vec<Story>,
+    /// Story id -> number of comments that weren't cached before this sync.
+    /// Only stories with at least one new reply are present.
+    pub new_reply_counts: HashMap<u64, usize>,
+}
+
+impl From<StorableSearchHit> for SearchHit {
+    fn from(hit: StorableSearchHit) -> Self {
+        Self {
+            kind: match hit.table.as_str() {
+                "comments" => SearchHitKind::Comment,
+                _ => SearchHitKind::Story,
+            },
+            id: hit.id,
+            story_id: hit.story_id,
+            snippet: hit.snippet,
+            score: hit.score,
+        }
+    }
+}
+
 pub struct HnClient {
     http: reqwest::Client,
-    storage: Option<Storage>,
+    storage: Option<Arc<dyn Cache>>,
+    max_concurrency: usize,
+    /// Shared across every call site on this client (and its clones) so the
+    /// in-flight request count stays bounded crate-wide, rather than each
+    /// `fetch_items_bounded` call getting its own full-size window.
+    semaphore: Arc<Semaphore>,
 }
 
 impl HnClient {
-    pub fn new(storage: Option<Storage>) -> Self {
+    pub fn new(storage: Option<Arc<dyn Cache>>) -> Self {
         Self {
             http: reqwest::Client::builder()
                 .timeout(Duration::from_secs(10))
                 .build()
                 .expect("Failed to create HTTP client"),
             storage,
+            max_concurrency: DEFAULT_MAX_CONCURRENCY,
+            semaphore: Arc::new(Semaphore::new(DEFAULT_MAX_CONCURRENCY)),
         }
     }
 
+    /// Caps how many requests this client has in flight at once. Useful for
+    /// turning the cap down on a flaky connection, or up when crawling from
+    /// somewhere with more headroom.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self.semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        self
+    }
+
     async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ApiError> {
         let response = self.http.get(url).send().await?;
         let status = response.status();
@@ -47,11 +139,65 @@ impl HnClient {
         self.get_json(&url).await
     }
 
+    /// The id of the most recently created item, used to checkpoint how far
+    /// `sync_updates` has caught up.
+    pub async fn fetch_maxitem(&self) -> Result<u64, ApiError> {
+        let url = format!("{}/maxitem.json", API_BASE);
+        self.get_json(&url).await
+    }
+
+    async fn fetch_updates(&self) -> Result<UpdatesResponse, ApiError> {
+        let url = format!("{}/updates.json", API_BASE);
+        self.get_json(&url).await
+    }
+
     async fn fetch_item(&self, id: u64) -> Result<HnItem, ApiError> {
         let url = format!("{}/item/{}.json", API_BASE, id);
         self.get_json(&url).await
     }
 
+    /// Fetches a single item, retrying transient failures with exponential
+    /// backoff and jitter instead of giving up on the first 429 or timeout.
+    async fn fetch_item_with_retry(&self, id: u64) -> Result<HnItem, ApiError> {
+        let mut attempt = 0;
+        loop {
+            match self.fetch_item(id).await {
+                Ok(item) => return Ok(item),
+                Err(err) if attempt < MAX_RETRIES && is_retryable(&err) => {
+                    let delay = RETRY_BASE_DELAY * 2u32.pow(attempt) + jitter(id, attempt);
+                    warn!(id, attempt, ?delay, %err, "retrying after transient error");
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+
+    /// Fetches many items at once, bounded by `max_concurrency` in-flight
+    /// requests and retrying each one individually, so a large batch never
+    /// floods the Firebase API and a handful of flaky requests don't take
+    /// the whole batch down with them. Results come back in arbitrary order,
+    /// paired with the id they were fetched for. Shares `self.semaphore`
+    /// with every other call site on this client, so concurrent batches
+    /// (e.g. a story page fetch racing a comment-tree walk) stay within one
+    /// crate-wide cap instead of each getting their own.
+    async fn fetch_items_bounded(&self, ids: &[u64]) -> Vec<(u64, Result<HnItem, ApiError>)> {
+        let mut futures = FuturesUnordered::new();
+        for &id in ids {
+            futures.push(async {
+                let _permit = self.semaphore.acquire().await.expect("semaphore not closed");
+                (id, self.fetch_item_with_retry(id).await)
+            });
+        }
+
+        let mut results = Vec::with_capacity(ids.len());
+        while let Some(result) = futures.next().await {
+            results.push(result);
+        }
+        results
+    }
+
     #[instrument(skip(self), fields(feed = %feed.label(), page))]
     pub async fn fetch_stories(
         &self,
@@ -82,16 +228,21 @@ impl HnClient {
         let mut stories = Vec::with_capacity(ids.len());
         let mut to_fetch = Vec::new();
 
-        // Check storage for cached stories (unless forcing refresh)
+        // Check storage for cached stories (unless forcing refresh). This is
+        // one `WHERE id IN (...)` query for the whole page rather than a
+        // round-trip per id.
         if !force_refresh {
             if let Some(storage) = &self.storage {
+                let mut cached = storage.get_fresh_stories(ids).await.unwrap_or_default();
+                debug!(
+                    hits = cached.len(),
+                    misses = ids.len() - cached.len(),
+                    "story cache batch lookup"
+                );
                 for &id in ids {
-                    if let Ok(Some(cached)) = storage.get_fresh_story(id).await {
-                        debug!(story_id = id, "cache hit");
-                        stories.push(cached.into());
-                    } else {
-                        debug!(story_id = id, "cache miss");
-                        to_fetch.push(id);
+                    match cached.remove(&id) {
+                        Some(story) => stories.push(story.into()),
+                        None => to_fetch.push(id),
                     }
                 }
             } else {
@@ -101,16 +252,17 @@ impl HnClient {
             to_fetch.extend_from_slice(ids);
         }
 
-        // Fetch remaining from API
+        // Fetch remaining from API, bounded and with retries
         if !to_fetch.is_empty() {
-            let futures: Vec<_> = to_fetch.iter().map(|&id| self.fetch_item(id)).collect();
-            let results = futures::future::join_all(futures).await;
+            let results = self.fetch_items_bounded(&to_fetch).await;
 
-            let fetched: Vec<Story> = results
-                .into_iter()
-                .filter_map(|r| r.ok())
-                .filter_map(Story::from_item)
-                .collect();
+            let mut fetched = Vec::with_capacity(results.len());
+            for (id, result) in results {
+                match result {
+                    Ok(item) => fetched.extend(Story::from_item(item)),
+                    Err(err) => warn!(id, %err, "giving up on story after retries"),
+                }
+            }
 
             // Write-through to storage
             if let Some(storage) = &self.storage {
@@ -158,20 +310,22 @@ impl HnClient {
         let mut depth = 0;
 
         while !to_fetch.is_empty() && depth <= max_depth {
-            let futures: Vec<_> = to_fetch.iter().map(|&id| self.fetch_item(id)).collect();
-            let results = futures::future::join_all(futures).await;
+            let results = self.fetch_items_bounded(&to_fetch).await;
 
             let mut next_fetch = Vec::new();
-            for (id, result) in to_fetch.into_iter().zip(results) {
+            for (id, result) in results {
                 attempted.insert(id);
-                if let Ok(item) = result {
-                    if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
-                        continue;
+                match result {
+                    Ok(item) => {
+                        if item.deleted.unwrap_or(false) || item.dead.unwrap_or(false) {
+                            continue;
+                        }
+                        if depth < max_depth {
+                            next_fetch.extend(&item.kids);
+                        }
+                        items.insert(id, item);
                     }
-                    if depth < max_depth {
-                        next_fetch.extend(&item.kids);
-                    }
-                    items.insert(id, item);
+                    Err(err) => warn!(id, %err, "giving up on comment after retries"),
                 }
             }
             to_fetch = next_fetch;
@@ -194,6 +348,107 @@ impl HnClient {
         info!(count = comments.len(), "fetched comments");
         Ok(comments)
     }
+
+    /// Searches cached story titles and comment bodies via the SQLite FTS5
+    /// index, most relevant (lowest BM25 score) first. Returns an
+    /// empty result set (rather than an error) when there's no local
+    /// storage to search, since search is purely an enhancement over the
+    /// cache.
+    #[instrument(skip(self), fields(query, limit))]
+    pub async fn search_local(&self, query: &str, limit: usize) -> Result<Vec<SearchHit>, ApiError> {
+        let Some(storage) = &self.storage else {
+            return Ok(Vec::new());
+        };
+
+        let hits = storage
+            .search(query, limit)
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+
+        Ok(hits.into_iter().map(SearchHit::from).collect())
+    }
+
+    /// Pulls `updates.json`, intersects the changed ids with what's already
+    /// cached, and selectively refetches just those stories (and, for any
+    /// that already have cached comments, re-crawls the comment tree too).
+    /// Everything else in the changed set is left alone: it'll be fetched
+    /// fresh the normal way whenever the user actually visits it. Advances
+    /// `last_synced_maxitem` on success so future syncs have a checkpoint.
+    #[instrument(skip(self))]
+    pub async fn sync_updates(&self) -> Result<SyncResult, ApiError> {
+        use std::collections::HashSet;
+
+        let Some(storage) = &self.storage else {
+            return Ok(SyncResult::default());
+        };
+
+        let max_item = self.fetch_maxitem().await?;
+        let last_synced = storage
+            .get_last_synced_maxitem()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        if last_synced.is_some_and(|last| max_item <= last) {
+            info!(max_item, "no new items since last sync, skipping updates diff");
+            return Ok(SyncResult::default());
+        }
+
+        let updates = self.fetch_updates().await?;
+        let changed: HashSet<u64> = updates.items.into_iter().collect();
+
+        let cached_ids = storage
+            .cached_story_ids()
+            .await
+            .map_err(|e| ApiError::Parse(e.to_string()))?;
+        let to_refresh: Vec<u64> = cached_ids.into_iter().filter(|id| changed.contains(id)).collect();
+
+        let mut result = SyncResult::default();
+        if !to_refresh.is_empty() {
+            info!(count = to_refresh.len(), "refreshing changed cached stories");
+            let stories = self.fetch_stories_by_ids(&to_refresh, true).await?;
+
+            for story in &stories {
+                let cached_count = storage
+                    .get_comments(story.id)
+                    .await
+                    .map(|comments| comments.len())
+                    .unwrap_or(0);
+                if cached_count > 0 {
+                    let refreshed = self.fetch_comments_flat(story, usize::MAX, true).await?;
+                    let new_replies = refreshed.len().saturating_sub(cached_count);
+                    if new_replies > 0 {
+                        result.new_reply_counts.insert(story.id, new_replies);
+                    }
+                }
+            }
+
+            result.updated_stories = stories;
+        }
+
+        let _ = storage.set_last_synced_maxitem(max_item).await;
+
+        Ok(result)
+    }
+}
+
+/// Whether an error is worth retrying: rate limiting, server-side failures,
+/// and (via the catch-all) network-level errors like timeouts. Malformed
+/// responses are not retried since a retry would just parse the same bytes
+/// again.
+fn is_retryable(err: &ApiError) -> bool {
+    match err {
+        ApiError::HttpStatus(status, _) => *status == 429 || (500..=599).contains(status),
+        ApiError::Parse(_) => false,
+        _ => true,
+    }
+}
+
+/// A few tens of milliseconds of deterministic jitter, so a burst of
+/// requests that all fail at once don't all retry in lockstep. Derived from
+/// the id and attempt count rather than a random crate, since it only needs
+/// to spread retries apart, not be unpredictable.
+fn jitter(id: u64, attempt: u32) -> Duration {
+    let seed = id.wrapping_mul(2654435761).wrapping_add(attempt as u64);
+    Duration::from_millis(seed % 50)
 }
 
 fn find_parent_id(comments: &[Comment], comment_id: u64) -> Option<u64> {
@@ -273,6 +528,8 @@ impl Clone for HnClient {
         Self {
             http: self.http.clone(),
             storage: self.storage.clone(),
+            max_concurrency: self.max_concurrency,
+            semaphore: self.semaphore.clone(),
         }
     }
 }
@@ -2,6 +2,6 @@ mod client;
 mod error;
 mod types;
 
-pub use client::HnClient;
+pub use client::{HnClient, SearchHit, SearchHitKind};
 pub use error::ApiError;
 pub use types::{Comment, Feed, Story};
@@ -0,0 +1,470 @@
+//! Application state: the single [`App`] struct the render functions read
+//! from and [`App::update`] mutates in response to an [`Action`].
+
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::Rect;
+use tokio::sync::mpsc::UnboundedSender;
+
+use crate::api::{Comment, Feed, HnClient, Story};
+use crate::event::{DataMsg, Event};
+use crate::keys::Action;
+use crate::theme::ResolvedTheme;
+
+/// Which screen is on top. A story's id/title/original index are carried
+/// along so leaving the comments view can restore the right selection, and
+/// a comment thread can be refreshed by looking its story up by id in the
+/// stories list.
+#[derive(Debug, Clone)]
+pub enum View {
+    Stories,
+    Comments {
+        story_id: u64,
+        story_title: String,
+        story_index: usize,
+        story_scroll: usize,
+    },
+}
+
+/// Two clicks this close together, in the same cell, count as a
+/// double-click rather than two independent selections.
+const DOUBLE_CLICK_WINDOW: Duration = Duration::from_millis(400);
+
+/// How many levels of replies `enter_comments`/`refresh` fetch. Deeper
+/// replies are not fetched; the comments view itself has no depth limit and
+/// renders whatever depth it is given.
+const MAX_COMMENT_DEPTH: usize = 8;
+
+/// `Rect`s the current view's renderer recorded this frame, so a mouse
+/// click can be hit-tested against them. Populated by `views::stories`/
+/// `views::comments` during `render`, which only ever sees `&App` - hence
+/// the `RefCell` rather than a `&mut App` render pass.
+#[derive(Debug, Default)]
+pub struct MouseRegions {
+    pub feed_tabs: Vec<(Rect, usize)>,
+    pub rows: Vec<Rect>,
+    /// Index into the current view's list that `rows[0]` corresponds to,
+    /// for views (like comments) that scroll their list.
+    pub rows_offset: usize,
+}
+
+pub struct App {
+    pub view: View,
+    pub theme: ResolvedTheme,
+
+    pub feed: Feed,
+    pub stories: Vec<Story>,
+    pub comments: Vec<Comment>,
+    /// Ids of comments whose replies are shown. A comment with no entry
+    /// here, but with non-empty `kids`, renders collapsed.
+    pub expanded_comments: HashSet<u64>,
+    pub selected_index: usize,
+
+    pub loading: bool,
+    pub loading_start: Option<Instant>,
+    pub error: Option<String>,
+
+    pub show_help: bool,
+    pub help_overlay: bool,
+    pub should_quit: bool,
+
+    pub mouse_regions: RefCell<MouseRegions>,
+    last_click: RefCell<Option<(u16, u16, Instant)>>,
+
+    /// Cloned onto each detached fetch task spawned by a key/mouse handler,
+    /// so `select_feed`/`enter_comments`/`refresh` never hold the client's
+    /// `.await` across a render.
+    client: HnClient,
+    /// Where those tasks post their `Event::Data` result back to the main
+    /// loop.
+    action_tx: UnboundedSender<Event>,
+}
+
+impl App {
+    pub fn new(theme: ResolvedTheme, action_tx: UnboundedSender<Event>, client: HnClient) -> Self {
+        Self::build(theme, action_tx, client)
+    }
+
+    #[cfg(test)]
+    pub(crate) fn new_for_test(theme: ResolvedTheme, action_tx: UnboundedSender<Event>) -> Self {
+        Self::build(theme, action_tx, HnClient::new(None))
+    }
+
+    fn build(theme: ResolvedTheme, action_tx: UnboundedSender<Event>, client: HnClient) -> Self {
+        let feed = Feed::all().into_iter().next().expect("at least one feed");
+        Self {
+            view: View::Stories,
+            theme,
+            feed,
+            stories: Vec::new(),
+            comments: Vec::new(),
+            expanded_comments: HashSet::new(),
+            selected_index: 0,
+            loading: true,
+            loading_start: Some(Instant::now()),
+            error: None,
+            show_help: false,
+            help_overlay: false,
+            should_quit: false,
+            mouse_regions: RefCell::new(MouseRegions::default()),
+            last_click: RefCell::new(None),
+            client,
+            action_tx,
+        }
+    }
+
+    /// Indices into `self.comments` that should currently render: every
+    /// comment whose ancestor chain is fully expanded. A collapsed
+    /// comment's own row still shows (so it can be re-expanded), but
+    /// everything under it is skipped until the first ancestor depth that's
+    /// no longer hidden.
+    pub fn visible_comment_indices(&self) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut hidden_below_depth: Option<usize> = None;
+
+        for (i, comment) in self.comments.iter().enumerate() {
+            if let Some(depth) = hidden_below_depth {
+                if comment.depth > depth {
+                    continue;
+                }
+                hidden_below_depth = None;
+            }
+
+            visible.push(i);
+            if !comment.kids.is_empty() && !self.expanded_comments.contains(&comment.id) {
+                hidden_below_depth = Some(comment.depth);
+            }
+        }
+
+        visible
+    }
+
+    pub async fn update(&mut self, action: Action) {
+        match action {
+            Action::Quit => self.should_quit = true,
+            Action::ToggleHelp => {
+                self.show_help = !self.show_help;
+                self.help_overlay = !self.help_overlay;
+            }
+            Action::MoveUp => self.move_selection(-1),
+            Action::MoveDown => self.move_selection(1),
+            Action::MoveTop => self.selected_index = 0,
+            Action::MoveBottom => self.selected_index = self.current_len().saturating_sub(1),
+            Action::SelectFeed(i) => self.select_feed(i),
+            Action::SelectIndex(i) => self.select_index(i),
+            Action::ActivateIndex(i) => self.activate_index(i),
+            Action::OpenInBrowser => self.open_in_browser(),
+            Action::EnterComments => self.enter_comments(),
+            Action::Expand => self.set_selected_expanded(true),
+            Action::Collapse => self.set_selected_expanded(false),
+            Action::ToggleExpand => self.toggle_selected_expanded(),
+            Action::CopyLink => self.copy_link(),
+            Action::Refresh => self.refresh(),
+            Action::Back => self.back_to_stories(),
+        }
+    }
+
+    /// Applies the outcome of a background fetch, wherever it was spawned
+    /// from - the initial stories load kicked off in `main`, or a feed
+    /// switch/refresh/comments fetch spawned by a handler via `action_tx`.
+    pub async fn handle_data(&mut self, msg: DataMsg) {
+        self.loading = false;
+        match msg {
+            DataMsg::StoriesLoaded(stories) => {
+                self.error = None;
+                self.stories = stories;
+            }
+            DataMsg::CommentsLoaded(comments) => {
+                self.error = None;
+                self.comments = comments;
+            }
+            DataMsg::FetchFailed(message) => self.error = Some(message),
+        }
+    }
+
+    fn current_len(&self) -> usize {
+        match &self.view {
+            View::Stories => self.stories.len(),
+            View::Comments { .. } => self.visible_comment_indices().len(),
+        }
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let len = self.current_len();
+        if len == 0 {
+            return;
+        }
+        let next = self.selected_index as isize + delta;
+        self.selected_index = next.clamp(0, len as isize - 1) as usize;
+    }
+
+    fn select_index(&mut self, index: usize) {
+        let len = self.current_len();
+        if len > 0 {
+            self.selected_index = index.min(len - 1);
+        }
+    }
+
+    fn activate_index(&mut self, index: usize) {
+        self.select_index(index);
+        match &self.view {
+            View::Stories => self.open_in_browser(),
+            View::Comments { .. } => self.toggle_selected_expanded(),
+        }
+    }
+
+    /// Switches the active feed and kicks off a fetch of its first page.
+    fn select_feed(&mut self, index: usize) {
+        let Some(&feed) = Feed::all().get(index) else {
+            return;
+        };
+        if feed == self.feed {
+            return;
+        }
+        self.feed = feed;
+        self.stories.clear();
+        self.selected_index = 0;
+        self.start_loading();
+        self.spawn_fetch_stories(feed, false);
+    }
+
+    fn enter_comments(&mut self) {
+        let Some(story) = self.stories.get(self.selected_index).cloned() else {
+            return;
+        };
+        self.view = View::Comments {
+            story_id: story.id,
+            story_title: story.title.clone(),
+            story_index: self.selected_index,
+            story_scroll: 0,
+        };
+        self.comments.clear();
+        self.expanded_comments.clear();
+        self.selected_index = 0;
+        self.start_loading();
+        self.spawn_fetch_comments(story, false);
+    }
+
+    fn back_to_stories(&mut self) {
+        if let View::Comments { story_index, .. } = &self.view {
+            self.selected_index = *story_index;
+        }
+        self.view = View::Stories;
+    }
+
+    /// Re-fetches whatever the current view is showing, bypassing the
+    /// cache so a manual refresh always reaches the network.
+    fn refresh(&mut self) {
+        match &self.view {
+            View::Stories => {
+                let feed = self.feed;
+                self.start_loading();
+                self.spawn_fetch_stories(feed, true);
+            }
+            View::Comments { story_id, .. } => {
+                let Some(story) = self.stories.iter().find(|s| s.id == *story_id).cloned() else {
+                    return;
+                };
+                self.start_loading();
+                self.spawn_fetch_comments(story, true);
+            }
+        }
+    }
+
+    fn start_loading(&mut self) {
+        self.loading = true;
+        self.loading_start = Some(Instant::now());
+        self.error = None;
+    }
+
+    /// Fetches `feed`'s first page on a detached task and posts the result
+    /// back as `Event::Data`, so `update` never holds the client's `.await`
+    /// across a render.
+    fn spawn_fetch_stories(&self, feed: Feed, force_refresh: bool) {
+        let client = self.client.clone();
+        let action_tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let msg = match client.fetch_stories(feed, 0, force_refresh).await {
+                Ok(stories) => DataMsg::StoriesLoaded(stories),
+                Err(e) => DataMsg::FetchFailed(e.to_string()),
+            };
+            let _ = action_tx.send(Event::Data(msg));
+        });
+    }
+
+    /// Fetches `story`'s comment tree on a detached task and posts the
+    /// result back as `Event::Data`.
+    fn spawn_fetch_comments(&self, story: Story, force_refresh: bool) {
+        let client = self.client.clone();
+        let action_tx = self.action_tx.clone();
+        tokio::spawn(async move {
+            let msg = match client
+                .fetch_comments_flat(&story, MAX_COMMENT_DEPTH, force_refresh)
+                .await
+            {
+                Ok(comments) => DataMsg::CommentsLoaded(comments),
+                Err(e) => DataMsg::FetchFailed(e.to_string()),
+            };
+            let _ = action_tx.send(Event::Data(msg));
+        });
+    }
+
+    fn set_selected_expanded(&mut self, expand: bool) {
+        let View::Comments { .. } = &self.view else {
+            return;
+        };
+        let visible = self.visible_comment_indices();
+        let Some(&idx) = visible.get(self.selected_index) else {
+            return;
+        };
+        let id = self.comments[idx].id;
+        if expand {
+            self.expanded_comments.insert(id);
+        } else {
+            self.expanded_comments.remove(&id);
+        }
+    }
+
+    fn toggle_selected_expanded(&mut self) {
+        let View::Comments { .. } = &self.view else {
+            return;
+        };
+        let visible = self.visible_comment_indices();
+        let Some(&idx) = visible.get(self.selected_index) else {
+            return;
+        };
+        let id = self.comments[idx].id;
+        if !self.expanded_comments.insert(id) {
+            self.expanded_comments.remove(&id);
+        }
+    }
+
+    fn open_in_browser(&self) {
+        let url = match &self.view {
+            View::Stories => self.stories.get(self.selected_index).and_then(|s| s.url.clone()),
+            View::Comments { story_id, .. } => Some(item_url(*story_id)),
+        };
+        if let Some(url) = url {
+            open_url(&url);
+        }
+    }
+
+    fn copy_link(&self) {
+        if let View::Comments { .. } = &self.view {
+            let visible = self.visible_comment_indices();
+            if let Some(&idx) = visible.get(self.selected_index) {
+                copy_to_clipboard(&item_url(self.comments[idx].id));
+            }
+        }
+    }
+
+    /// Whether a click at `(column, row)` is close enough in time and
+    /// position to the last one to count as a double-click. Resets the
+    /// tracked click on a hit so a third click starts a fresh pair rather
+    /// than double-triggering.
+    fn is_double_click(&self, column: u16, row: u16) -> bool {
+        let now = Instant::now();
+        let mut last_click = self.last_click.borrow_mut();
+        let is_double = matches!(
+            *last_click,
+            Some((c, r, t)) if c == column && r == row && now.duration_since(t) < DOUBLE_CLICK_WINDOW
+        );
+        *last_click = if is_double { None } else { Some((column, row, now)) };
+        is_double
+    }
+}
+
+fn item_url(id: u64) -> String {
+    format!("https://news.ycombinator.com/item?id={id}")
+}
+
+/// Opens `url` in the user's default browser via the platform's standard
+/// "open this" command, logging rather than erroring on failure - a broken
+/// browser launch shouldn't take down the TUI.
+fn open_url(url: &str) {
+    let result = if cfg!(target_os = "macos") {
+        std::process::Command::new("open").arg(url).spawn()
+    } else if cfg!(target_os = "windows") {
+        std::process::Command::new("cmd").args(["/C", "start", "", url]).spawn()
+    } else {
+        std::process::Command::new("xdg-open").arg(url).spawn()
+    };
+    if let Err(e) = result {
+        tracing::warn!("failed to open {url} in browser: {e}");
+    }
+}
+
+/// Copies `text` to the system clipboard via the platform's clipboard CLI,
+/// logging rather than erroring on failure.
+fn copy_to_clipboard(text: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("pbcopy")
+    } else if cfg!(target_os = "windows") {
+        std::process::Command::new("clip")
+    } else {
+        let mut cmd = std::process::Command::new("xclip");
+        cmd.args(["-selection", "clipboard"]);
+        cmd
+    };
+
+    match command.stdin(std::process::Stdio::piped()).spawn() {
+        Ok(mut child) => {
+            if let Some(stdin) = child.stdin.as_mut() {
+                if let Err(e) = stdin.write_all(text.as_bytes()) {
+                    tracing::warn!("failed to write to clipboard command: {e}");
+                }
+            }
+        }
+        Err(e) => tracing::warn!("failed to copy to clipboard: {e}"),
+    }
+}
+
+/// Translates a mouse event into the [`Action`] it should perform, using
+/// the `Rect`s the last render recorded in `app.mouse_regions`.
+pub fn handle_mouse(mouse: MouseEvent, app: &App) -> Option<Action> {
+    match mouse.kind {
+        MouseEventKind::ScrollUp => Some(Action::MoveUp),
+        MouseEventKind::ScrollDown => Some(Action::MoveDown),
+        MouseEventKind::Down(MouseButton::Left) => handle_left_click(mouse.column, mouse.row, app),
+        _ => None,
+    }
+}
+
+fn handle_left_click(column: u16, row: u16, app: &App) -> Option<Action> {
+    let hit = {
+        let regions = app.mouse_regions.borrow();
+
+        if let Some(&(_, feed_index)) = regions
+            .feed_tabs
+            .iter()
+            .find(|(rect, _)| rect_contains(*rect, column, row))
+        {
+            Hit::FeedTab(feed_index)
+        } else if let Some(row_index) = regions.rows.iter().position(|rect| rect_contains(*rect, column, row)) {
+            Hit::Row(regions.rows_offset + row_index)
+        } else {
+            Hit::None
+        }
+    };
+
+    match hit {
+        Hit::FeedTab(feed_index) => Some(Action::SelectFeed(feed_index)),
+        Hit::Row(index) if app.is_double_click(column, row) => Some(Action::ActivateIndex(index)),
+        Hit::Row(index) => Some(Action::SelectIndex(index)),
+        Hit::None => None,
+    }
+}
+
+enum Hit {
+    FeedTab(usize),
+    Row(usize),
+    None,
+}
+
+fn rect_contains(rect: Rect, column: u16, row: u16) -> bool {
+    column >= rect.x && column < rect.x + rect.width && row >= rect.y && row < rect.y + rect.height
+}
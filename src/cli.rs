@@ -0,0 +1,73 @@
+//! Command-line interface: flags for the TUI itself, plus the `theme`
+//! subcommand for inspecting built-in and custom themes without starting it.
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand, ValueEnum};
+
+#[derive(Debug, Parser)]
+#[command(name = "lima-hn", about = "A terminal client for Hacker News")]
+pub struct Cli {
+    /// Force the dark built-in theme, skipping terminal-background detection.
+    #[arg(long, conflicts_with = "light")]
+    pub dark: bool,
+
+    /// Force the light built-in theme, skipping terminal-background detection.
+    #[arg(long, conflicts_with = "dark")]
+    pub light: bool,
+
+    /// A built-in theme name, a custom theme name, or a path to a theme
+    /// TOML file. Overrides `--dark`/`--light` and auto-detection.
+    #[arg(long)]
+    pub theme: Option<String>,
+
+    #[command(subcommand)]
+    pub command: Option<Commands>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Commands {
+    /// Inspect built-in and custom themes.
+    Theme(ThemeArgs),
+}
+
+#[derive(Debug, Parser)]
+pub struct ThemeArgs {
+    #[command(subcommand)]
+    pub command: ThemeCommands,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum ThemeCommands {
+    /// List built-in themes.
+    List {
+        /// Show each theme's variant and description alongside its name.
+        #[arg(short, long)]
+        verbose: bool,
+    },
+    /// Print a built-in theme's definition.
+    Show {
+        name: String,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Toml)]
+        format: OutputFormat,
+    },
+    /// Print the custom themes directory path.
+    Path,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    Toml,
+    Json,
+}
+
+/// Where custom theme TOML files live: `$XDG_CONFIG_HOME/hn/themes`, falling
+/// back to `~/.config/hn/themes` when `XDG_CONFIG_HOME` isn't set. `None`
+/// when neither can be determined (e.g. `HOME` is unset).
+pub fn custom_themes_dir() -> Option<PathBuf> {
+    if let Some(xdg) = std::env::var_os("XDG_CONFIG_HOME") {
+        return Some(PathBuf::from(xdg).join("hn").join("themes"));
+    }
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".config").join("hn").join("themes"))
+}
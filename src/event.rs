@@ -1,4 +1,6 @@
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyEvent, MouseEvent};
+
+use crate::api::{Comment, Story};
 
 /// Application events
 #[derive(Debug, Clone)]
@@ -7,8 +9,19 @@ pub enum Event {
     Tick,
     /// Keyboard input
     Key(KeyEvent),
-    /// Mouse input (ignored)
-    Mouse,
+    /// Mouse input (clicks, scroll wheel)
+    Mouse(MouseEvent),
     /// Terminal resize (ignored)
     Resize,
+    /// A background fetch spawned by a key/mouse handler has completed
+    Data(DataMsg),
+}
+
+/// Outcome of a background `HnClient` fetch, posted back onto the event
+/// channel so the task that performed it never has to touch `App` directly.
+#[derive(Debug, Clone)]
+pub enum DataMsg {
+    StoriesLoaded(Vec<Story>),
+    CommentsLoaded(Vec<Comment>),
+    FetchFailed(String),
 }
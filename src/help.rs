@@ -0,0 +1,56 @@
+//! Declarative help-overlay content: which [`Action`]s are worth surfacing
+//! in the `?` popup and the label shown next to each, independent of which
+//! physical key they're bound to (that's [`Keymap`]).
+
+use crate::keys::{Action, Keymap};
+
+/// One row of the help overlay: an action and its human label. The key
+/// label itself comes from looking `action` up in the active [`Keymap`] at
+/// render time, so the overlay never goes stale relative to the bindings.
+pub struct HelpItem {
+    action: Action,
+    label: &'static str,
+}
+
+impl HelpItem {
+    const fn new(action: Action, label: &'static str) -> Self {
+        Self { action, label }
+    }
+
+    /// Formats this item for display, or `None` if `keymap` has no key
+    /// bound to its action (nothing to show next to the label).
+    pub fn format_for_overlay(&self, keymap: &Keymap) -> Option<(String, &'static str)> {
+        let keys = keymap.keys_for(self.action)?;
+        Some((keys.to_string(), self.label))
+    }
+}
+
+pub fn stories_overlay_items() -> Vec<HelpItem> {
+    vec![
+        HelpItem::new(Action::MoveUp, "Move up"),
+        HelpItem::new(Action::MoveDown, "Move down"),
+        HelpItem::new(Action::MoveTop, "Jump to top"),
+        HelpItem::new(Action::MoveBottom, "Jump to bottom"),
+        HelpItem::new(Action::EnterComments, "Open comments"),
+        HelpItem::new(Action::OpenInBrowser, "Open story in browser"),
+        HelpItem::new(Action::SelectFeed(0), "Switch feed"),
+        HelpItem::new(Action::Refresh, "Refresh"),
+        HelpItem::new(Action::ToggleHelp, "Toggle this help"),
+        HelpItem::new(Action::Quit, "Quit"),
+    ]
+}
+
+pub fn comments_overlay_items() -> Vec<HelpItem> {
+    vec![
+        HelpItem::new(Action::MoveUp, "Move up"),
+        HelpItem::new(Action::MoveDown, "Move down"),
+        HelpItem::new(Action::Expand, "Expand"),
+        HelpItem::new(Action::Collapse, "Collapse"),
+        HelpItem::new(Action::OpenInBrowser, "Open story in browser"),
+        HelpItem::new(Action::CopyLink, "Copy comment link"),
+        HelpItem::new(Action::Refresh, "Refresh"),
+        HelpItem::new(Action::Back, "Back to stories"),
+        HelpItem::new(Action::ToggleHelp, "Toggle this help"),
+        HelpItem::new(Action::Quit, "Quit"),
+    ]
+}
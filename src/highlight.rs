@@ -0,0 +1,162 @@
+//! Syntax highlighting for `<pre><code>` blocks in comment bodies.
+//!
+//! HN comments often paste shell snippets, Rust, or other code inside a
+//! `<pre>` tag with no language annotation. This module leans on `syntect`
+//! to turn that plain text into colored [`Line`]s: a [`SyntaxSet`] and
+//! [`ThemeSet`] are loaded once (they're a few megabytes of bundled
+//! grammars/themes, not something you want to parse per comment), a syntax
+//! is guessed from the code's shape, and each line is run through
+//! `HighlightLines` and converted into spans whose color comes straight
+//! from syntect's style. Highlighting is the expensive part of rendering a
+//! code block, so results are cached per `(comment id, block index)`;
+//! scrolling the comment list re-renders the same blocks over and over and
+//! should never re-run the highlighter for content that hasn't changed.
+
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{Style as SynStyle, Theme, ThemeSet};
+use syntect::parsing::{SyntaxReference, SyntaxSet};
+use syntect::util::LinesWithEndings;
+
+use crate::theme::ResolvedTheme;
+
+fn syntax_set() -> &'static SyntaxSet {
+    static SET: OnceLock<SyntaxSet> = OnceLock::new();
+    SET.get_or_init(SyntaxSet::load_defaults_newlines)
+}
+
+fn theme_set() -> &'static ThemeSet {
+    static SET: OnceLock<ThemeSet> = OnceLock::new();
+    SET.get_or_init(ThemeSet::load_defaults)
+}
+
+type CacheKey = (u64, usize);
+
+fn cache() -> &'static Mutex<HashMap<CacheKey, Vec<Line<'static>>>> {
+    static CACHE: OnceLock<Mutex<HashMap<CacheKey, Vec<Line<'static>>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Syntax-highlights a `<pre>` block's contents line by line, using a
+/// cached result when this exact `(comment_id, block_index)` has already
+/// been highlighted.
+///
+/// `block_index` distinguishes multiple `<pre>` blocks within the same
+/// comment, since a comment id alone isn't a unique key once a comment has
+/// more than one code block.
+pub fn highlight_pre_block(
+    comment_id: u64,
+    block_index: usize,
+    code: &str,
+    theme: &ResolvedTheme,
+) -> Vec<Line<'static>> {
+    let key = (comment_id, block_index);
+    if let Some(cached) = cache().lock().unwrap().get(&key) {
+        return cached.clone();
+    }
+
+    let ss = syntax_set();
+    let syntax = guess_syntax(code, ss);
+    let syn_theme = pick_syntect_theme(theme);
+    let fallback = Style::default().fg(theme.code_text);
+
+    let mut highlighter = HighlightLines::new(syntax, syn_theme);
+    let mut lines = Vec::new();
+    for line in LinesWithEndings::from(code) {
+        let ranges = highlighter.highlight_line(line, ss).unwrap_or_default();
+        let spans = ranges
+            .into_iter()
+            .map(|(style, text)| {
+                Span::styled(
+                    text.trim_end_matches(['\n', '\r']).to_string(),
+                    to_ratatui_style(style, fallback),
+                )
+            })
+            .collect::<Vec<_>>();
+        lines.push(Line::from(spans));
+    }
+
+    cache().lock().unwrap().insert(key, lines.clone());
+    lines
+}
+
+/// Best-effort guess at which syntax definition fits `code`. HN code blocks
+/// never carry a language hint, so this tries a shebang/first-line match
+/// first (the one signal syntect itself understands) and falls back to a
+/// handful of keyword sniffs for the languages that show up most often in
+/// HN threads, before giving up to plain text.
+fn guess_syntax<'a>(code: &str, ss: &'a SyntaxSet) -> &'a SyntaxReference {
+    if let Some(first_line) = code.lines().next() {
+        if let Some(syntax) = ss.find_syntax_by_first_line(first_line) {
+            return syntax;
+        }
+    }
+
+    const KEYWORD_HINTS: &[(&str, &str)] = &[
+        ("fn main", "rs"),
+        ("impl ", "rs"),
+        ("def ", "py"),
+        ("import ", "py"),
+        ("#include", "cpp"),
+        ("func ", "go"),
+        ("package ", "go"),
+        ("<?php", "php"),
+        ("SELECT ", "sql"),
+        ("function ", "js"),
+        ("const ", "js"),
+    ];
+    for (needle, ext) in KEYWORD_HINTS {
+        if code.contains(needle) {
+            if let Some(syntax) = ss.find_syntax_by_extension(ext) {
+                return syntax;
+            }
+        }
+    }
+
+    ss.find_syntax_plain_text()
+}
+
+/// Picks a bundled syntect theme that roughly matches the active color
+/// theme, guessed from the brightness of its own body-text color (a light
+/// theme's text is dark for contrast against a light background, and vice
+/// versa).
+fn pick_syntect_theme(theme: &ResolvedTheme) -> &'static Theme {
+    let themes = &theme_set().themes;
+    let name = if is_dark_text(theme.comment_text) {
+        "base16-ocean.light"
+    } else {
+        "base16-ocean.dark"
+    };
+    themes.get(name).unwrap_or_else(|| {
+        themes
+            .values()
+            .next()
+            .expect("syntect ThemeSet::load_defaults() always bundles at least one theme")
+    })
+}
+
+fn is_dark_text(color: Color) -> bool {
+    match color {
+        Color::Rgb(r, g, b) => {
+            let luma = 0.2126 * r as f32 + 0.7152 * g as f32 + 0.0722 * b as f32;
+            luma < 128.0
+        }
+        Color::Black | Color::DarkGray => true,
+        _ => false,
+    }
+}
+
+/// Converts a syntect style into its ratatui equivalent, falling back to
+/// the crate's own code color when syntect reports fully-transparent black
+/// (its way of saying "no style applies here").
+fn to_ratatui_style(style: SynStyle, fallback: Style) -> Style {
+    let fg = style.foreground;
+    if fg.a == 0 && fg.r == 0 && fg.g == 0 && fg.b == 0 {
+        return fallback;
+    }
+    Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b))
+}
@@ -0,0 +1,149 @@
+//! Translates raw input (keyboard, mouse) into the [`Action`]s [`App::update`]
+//! understands, and the [`Keymap`] tables the help overlay reads back to
+//! show which keys are bound to what, so the binding itself lives in exactly
+//! one place.
+//!
+//! [`App::update`]: crate::app::App::update
+
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind};
+
+use crate::app::{App, View};
+
+/// Something the user asked the app to do, decoupled from *how* they asked
+/// (keyboard vs mouse), so [`App::update`] has one place to change state.
+///
+/// [`App::update`]: crate::app::App::update
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Quit,
+    ToggleHelp,
+    MoveUp,
+    MoveDown,
+    MoveTop,
+    MoveBottom,
+    SelectFeed(usize),
+    /// Select a row by its index into the current view's list (stories, or
+    /// the visible comment list), without otherwise acting on it.
+    SelectIndex(usize),
+    /// Select a row and perform that view's primary action on it: open the
+    /// story's URL, or toggle the comment's expansion.
+    ActivateIndex(usize),
+    OpenInBrowser,
+    EnterComments,
+    Expand,
+    Collapse,
+    ToggleExpand,
+    CopyLink,
+    Refresh,
+    Back,
+}
+
+/// A table of `(action, key label)` pairs, used only to drive the help
+/// overlay's "which key does this" display; actual dispatch happens in
+/// [`handle_key`], independently of what's registered here.
+#[derive(Debug, Clone, Default)]
+pub struct Keymap(Vec<(Action, &'static str)>);
+
+impl Keymap {
+    fn new(bindings: &[(Action, &'static str)]) -> Self {
+        Self(bindings.to_vec())
+    }
+
+    /// Combines two keymaps, e.g. the global keymap with a view's own.
+    pub fn extend(mut self, other: Keymap) -> Keymap {
+        self.0.extend(other.0);
+        self
+    }
+
+    /// The key label registered for `action`, if any.
+    pub fn keys_for(&self, action: Action) -> Option<&'static str> {
+        self.0.iter().find(|(a, _)| *a == action).map(|(_, k)| *k)
+    }
+}
+
+/// Keys that behave the same in every view.
+pub fn global_keymap() -> Keymap {
+    Keymap::new(&[(Action::Quit, "q"), (Action::ToggleHelp, "?")])
+}
+
+pub fn stories_keymap() -> Keymap {
+    Keymap::new(&[
+        (Action::MoveUp, "k"),
+        (Action::MoveDown, "j"),
+        (Action::MoveTop, "g"),
+        (Action::MoveBottom, "G"),
+        (Action::OpenInBrowser, "o"),
+        (Action::EnterComments, "l"),
+        (Action::SelectFeed(0), "1-6"),
+        (Action::Refresh, "r"),
+    ])
+}
+
+pub fn comments_keymap() -> Keymap {
+    Keymap::new(&[
+        (Action::MoveUp, "k"),
+        (Action::MoveDown, "j"),
+        (Action::Expand, "l"),
+        (Action::Collapse, "h"),
+        (Action::OpenInBrowser, "o"),
+        (Action::CopyLink, "c"),
+        (Action::Refresh, "r"),
+        (Action::Back, "Esc"),
+    ])
+}
+
+/// Dispatches a key press to an [`Action`], or `None` if it has no meaning
+/// in the app's current view. Keys that behave the same everywhere (quit,
+/// toggle help) are checked before view-specific ones.
+pub fn handle_key(key: KeyEvent, app: &App) -> Option<Action> {
+    if key.kind != KeyEventKind::Press {
+        return None;
+    }
+
+    if let Some(action) = handle_global_key(key) {
+        return Some(action);
+    }
+
+    match &app.view {
+        View::Stories => handle_stories_key(key),
+        View::Comments { .. } => handle_comments_key(key),
+    }
+}
+
+fn handle_global_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('q') => Some(Action::Quit),
+        KeyCode::Char('?') => Some(Action::ToggleHelp),
+        _ => None,
+    }
+}
+
+fn handle_stories_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Some(Action::MoveDown),
+        KeyCode::Char('k') | KeyCode::Up => Some(Action::MoveUp),
+        KeyCode::Char('g') => Some(Action::MoveTop),
+        KeyCode::Char('G') => Some(Action::MoveBottom),
+        KeyCode::Char('o') => Some(Action::OpenInBrowser),
+        KeyCode::Char('l') | KeyCode::Enter => Some(Action::EnterComments),
+        KeyCode::Char('r') => Some(Action::Refresh),
+        KeyCode::Char(c @ '1'..='6') => {
+            Some(Action::SelectFeed(c.to_digit(10).unwrap() as usize - 1))
+        }
+        _ => None,
+    }
+}
+
+fn handle_comments_key(key: KeyEvent) -> Option<Action> {
+    match key.code {
+        KeyCode::Char('j') | KeyCode::Down => Some(Action::MoveDown),
+        KeyCode::Char('k') | KeyCode::Up => Some(Action::MoveUp),
+        KeyCode::Char('l') => Some(Action::Expand),
+        KeyCode::Char('h') => Some(Action::Collapse),
+        KeyCode::Char('o') => Some(Action::OpenInBrowser),
+        KeyCode::Char('c') => Some(Action::CopyLink),
+        KeyCode::Char('r') => Some(Action::Refresh),
+        KeyCode::Esc => Some(Action::Back),
+        _ => None,
+    }
+}
@@ -2,20 +2,30 @@ mod api;
 mod app;
 mod cli;
 mod event;
+mod help;
+mod highlight;
 mod keys;
+mod storage;
+mod time;
 mod theme;
 mod tui;
 mod views;
 
-use std::path::Path;
+#[cfg(test)]
+mod test_utils;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 use anyhow::{Context, Result};
 use clap::Parser;
 use ratatui::Frame;
 
+use api::HnClient;
 use app::{App, View};
 use cli::{Cli, Commands, OutputFormat, ThemeArgs, ThemeCommands};
-use event::Event;
+use event::{DataMsg, Event};
+use storage::{Cache, Storage, StorageLocation};
 use theme::{
     all_themes, by_name, default_for_variant, detect_terminal_theme, load_theme_file, ResolvedTheme,
     ThemeVariant,
@@ -128,15 +138,25 @@ async fn run_tui(cli: Cli) -> Result<()> {
     // Resolve theme from CLI args
     let resolved_theme = resolve_theme(&cli)?;
 
+    // Restore the terminal on panic, and on normal/early return via the guard below
+    tui::install_panic_hook();
+
     // Initialize terminal
-    let mut terminal = tui::init()?;
+    let (mut terminal, _guard) = tui::init()?;
+    tui::spawn_signal_handler();
 
     // Create app and event handler
-    let mut app = App::new(resolved_theme);
     let mut events = EventHandler::new(250); // 250ms tick rate
+    let storage = open_storage();
+    let client = HnClient::new(storage);
+    let mut app = App::new(resolved_theme, events.action_sender(), client.clone());
 
-    // Load initial stories
-    app.load_stories().await;
+    // Kick off the initial fetch in the background: the render loop starts
+    // spinning immediately and picks up the result via `Event::Data` once
+    // it arrives, instead of blocking the first frame on the HTTP round trip.
+    // Cloning `client` rather than building a second `HnClient` keeps both
+    // call sites sharing the one concurrency-limiting semaphore.
+    spawn_stories_fetch(app.feed, events.action_sender(), client);
 
     // Main loop
     loop {
@@ -156,8 +176,13 @@ async fn run_tui(cli: Cli) -> Result<()> {
             Event::Resize => {
                 // Terminal handles resize automatically
             }
-            Event::Mouse => {
-                // Mouse support can be added later
+            Event::Mouse(mouse) => {
+                if let Some(action) = app::handle_mouse(mouse, &app) {
+                    app.update(action).await;
+                }
+            }
+            Event::Data(data) => {
+                app.handle_data(data).await;
             }
         }
 
@@ -166,12 +191,54 @@ async fn run_tui(cli: Cli) -> Result<()> {
         }
     }
 
-    // Restore terminal
-    tui::restore()?;
-
+    // `_guard` restores the terminal when it drops here (or on early return above)
     Ok(())
 }
 
+/// Opens the on-disk story/comment cache, falling back to an in-memory one
+/// (still a real `Cache`, just gone on exit) if the cache directory can't be
+/// determined or the database file can't be opened, so a broken disk cache
+/// never blocks the TUI from starting.
+pub(crate) fn open_storage() -> Option<Arc<dyn Cache>> {
+    if let Some(path) = storage_db_path() {
+        if let Some(parent) = path.parent() {
+            if let Err(e) = std::fs::create_dir_all(parent) {
+                tracing::warn!("failed to create storage dir {}: {e}", parent.display());
+            }
+        }
+        match Storage::open(StorageLocation::File(&path)) {
+            Ok(storage) => return Some(Arc::new(storage)),
+            Err(e) => tracing::warn!("failed to open storage at {}: {e}", path.display()),
+        }
+    }
+
+    Storage::open(StorageLocation::InMemory)
+        .map(|storage| Arc::new(storage) as Arc<dyn Cache>)
+        .ok()
+}
+
+fn storage_db_path() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME")?;
+    Some(PathBuf::from(home).join(".cache").join("hn").join("hn.db"))
+}
+
+/// Fetches the first page of `feed` on a detached task and posts the
+/// result back as `Event::Data`, so callers never hold `&mut App` across
+/// the `.await`.
+fn spawn_stories_fetch(
+    feed: api::Feed,
+    action_tx: tokio::sync::mpsc::UnboundedSender<Event>,
+    client: HnClient,
+) {
+    tokio::spawn(async move {
+        let msg = match client.fetch_stories(feed, 0, false).await {
+            Ok(stories) => DataMsg::StoriesLoaded(stories),
+            Err(e) => DataMsg::FetchFailed(e.to_string()),
+        };
+        let _ = action_tx.send(Event::Data(msg));
+    });
+}
+
 fn render(app: &App, frame: &mut Frame) {
     let area = frame.area();
 
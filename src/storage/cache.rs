@@ -0,0 +1,187 @@
+//! The subset of `Storage` that `HnClient` actually depends on, pulled out
+//! into a trait so the client can run against something other than a SQLite
+//! file: no cache at all (`None`), an in-memory `HashMap` for tests, or a
+//! shared/remote store down the line, all without touching the fetch logic
+//! in `api::client`.
+//!
+//! Trait methods return boxed futures rather than `async fn` so `Cache` stays
+//! object-safe and callers can hold a plain `Arc<dyn Cache>`.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use super::{StorableComment, StorableSearchHit, StorableStory, StorageError};
+
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+pub trait Cache: Send + Sync {
+    fn get_fresh_stories<'a>(
+        &'a self,
+        ids: &'a [u64],
+    ) -> BoxFuture<'a, Result<HashMap<u64, StorableStory>, StorageError>>;
+
+    fn save_story<'a>(&'a self, story: &'a StorableStory) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    fn get_fresh_comments(
+        &self,
+        story_id: u64,
+    ) -> BoxFuture<'_, Result<Option<Vec<StorableComment>>, StorageError>>;
+
+    /// Batched form of [`Cache::get_fresh_comments`]: one lookup across all
+    /// of `story_ids` rather than one round trip per story.
+    fn get_fresh_comments_batch<'a>(
+        &'a self,
+        story_ids: &'a [u64],
+    ) -> BoxFuture<'a, Result<HashMap<u64, Vec<StorableComment>>, StorageError>>;
+
+    fn get_comments(&self, story_id: u64) -> BoxFuture<'_, Result<Vec<StorableComment>, StorageError>>;
+
+    fn save_comments<'a>(
+        &'a self,
+        story_id: u64,
+        comments: &'a [StorableComment],
+    ) -> BoxFuture<'a, Result<(), StorageError>>;
+
+    fn search<'a>(
+        &'a self,
+        query: &'a str,
+        limit: usize,
+    ) -> BoxFuture<'a, Result<Vec<StorableSearchHit>, StorageError>>;
+
+    fn cached_story_ids(&self) -> BoxFuture<'_, Result<Vec<u64>, StorageError>>;
+
+    fn set_last_synced_maxitem(&self, max_item: u64) -> BoxFuture<'_, Result<(), StorageError>>;
+
+    /// The `maxitem` id as of the last successful `sync_updates`, if one has
+    /// ever completed, so a sync can tell "nothing new since last time"
+    /// without re-diffing `updates.json` against the whole cache.
+    fn get_last_synced_maxitem(&self) -> BoxFuture<'_, Result<Option<u64>, StorageError>>;
+}
+
+/// A cache-less-to-cache-lite backend that keeps everything in process
+/// memory. Useful for tests and for running the client without a SQLite
+/// file on disk; comments are stored flat per story since nothing here
+/// needs to query across stories.
+#[derive(Default)]
+pub struct InMemoryCache {
+    stories: Mutex<HashMap<u64, StorableStory>>,
+    comments: Mutex<HashMap<u64, Vec<StorableComment>>>,
+    last_synced_maxitem: Mutex<Option<u64>>,
+}
+
+impl InMemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Cache for InMemoryCache {
+    fn get_fresh_stories<'a>(
+        &'a self,
+        ids: &'a [u64],
+    ) -> BoxFuture<'a, Result<HashMap<u64, StorableStory>, StorageError>> {
+        Box::pin(async move {
+            let cutoff = super::FRESHNESS_TTL_SECS;
+            let now = crate::time::now_unix();
+            let stories = self.stories.lock().unwrap();
+            Ok(ids
+                .iter()
+                .filter_map(|id| stories.get(id))
+                .filter(|s| now.saturating_sub(s.fetched_at) < cutoff)
+                .map(|s| (s.id, s.clone()))
+                .collect())
+        })
+    }
+
+    fn save_story<'a>(&'a self, story: &'a StorableStory) -> BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            self.stories.lock().unwrap().insert(story.id, story.clone());
+            Ok(())
+        })
+    }
+
+    fn get_fresh_comments(
+        &self,
+        story_id: u64,
+    ) -> BoxFuture<'_, Result<Option<Vec<StorableComment>>, StorageError>> {
+        Box::pin(async move {
+            let cutoff = super::FRESHNESS_TTL_SECS;
+            let now = crate::time::now_unix();
+            let comments = self.comments.lock().unwrap();
+            Ok(comments.get(&story_id).filter(|rows| {
+                rows.first().is_some_and(|c| now.saturating_sub(c.fetched_at) < cutoff)
+            }).cloned())
+        })
+    }
+
+    fn get_fresh_comments_batch<'a>(
+        &'a self,
+        story_ids: &'a [u64],
+    ) -> BoxFuture<'a, Result<HashMap<u64, Vec<StorableComment>>, StorageError>> {
+        Box::pin(async move {
+            let cutoff = super::FRESHNESS_TTL_SECS;
+            let now = crate::time::now_unix();
+            let comments = self.comments.lock().unwrap();
+            Ok(story_ids
+                .iter()
+                .filter_map(|id| comments.get(id).map(|rows| (*id, rows)))
+                .filter(|(_, rows)| rows.first().is_some_and(|c| now.saturating_sub(c.fetched_at) < cutoff))
+                .map(|(id, rows)| (id, rows.clone()))
+                .collect())
+        })
+    }
+
+    fn get_comments(&self, story_id: u64) -> BoxFuture<'_, Result<Vec<StorableComment>, StorageError>> {
+        Box::pin(async move {
+            Ok(self
+                .comments
+                .lock()
+                .unwrap()
+                .get(&story_id)
+                .cloned()
+                .unwrap_or_default())
+        })
+    }
+
+    fn save_comments<'a>(
+        &'a self,
+        story_id: u64,
+        comments: &'a [StorableComment],
+    ) -> BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            self.comments
+                .lock()
+                .unwrap()
+                .insert(story_id, comments.to_vec());
+            Ok(())
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        _query: &'a str,
+        _limit: usize,
+    ) -> BoxFuture<'a, Result<Vec<StorableSearchHit>, StorageError>> {
+        // Full-text search is a SQLite FTS5 feature; the in-memory backend
+        // has no index to search, so it always reports no matches rather
+        // than erroring.
+        Box::pin(async move { Ok(Vec::new()) })
+    }
+
+    fn cached_story_ids(&self) -> BoxFuture<'_, Result<Vec<u64>, StorageError>> {
+        Box::pin(async move { Ok(self.stories.lock().unwrap().keys().copied().collect()) })
+    }
+
+    fn set_last_synced_maxitem(&self, max_item: u64) -> BoxFuture<'_, Result<(), StorageError>> {
+        Box::pin(async move {
+            *self.last_synced_maxitem.lock().unwrap() = Some(max_item);
+            Ok(())
+        })
+    }
+
+    fn get_last_synced_maxitem(&self) -> BoxFuture<'_, Result<Option<u64>, StorageError>> {
+        Box::pin(async move { Ok(*self.last_synced_maxitem.lock().unwrap()) })
+    }
+}
@@ -0,0 +1,597 @@
+//! SQLite-backed persistence for `HnClient`: the `Storable*` DTOs that
+//! translate between API types and SQL rows, `Storage` itself, and the
+//! `Cache` trait (in [`cache`]) that lets the client run against it or
+//! against a plain in-memory store.
+
+mod cache;
+pub mod migrations;
+
+use std::path::Path;
+use std::sync::Mutex;
+
+use rusqlite::{params, Connection, OptionalExtension};
+
+pub use cache::{Cache, InMemoryCache};
+
+use crate::api::{Comment, Story};
+
+/// Where a [`Storage`]'s SQLite database lives.
+pub enum StorageLocation<'a> {
+    /// A file on disk, created (along with the schema) if it doesn't exist.
+    File(&'a Path),
+    /// An ephemeral in-memory database, gone once `Storage` drops. Used by
+    /// tests and as the fallback when no cache directory is available.
+    InMemory,
+}
+
+/// SQLite-backed [`Cache`]. The connection is wrapped in a `Mutex` since
+/// `rusqlite::Connection` isn't `Sync`; every query here is cheap enough
+/// that serializing access isn't a bottleneck for a single-user TUI.
+pub struct Storage {
+    conn: Mutex<Connection>,
+}
+
+impl Storage {
+    pub fn open(location: StorageLocation) -> Result<Self, StorageError> {
+        let conn = match location {
+            StorageLocation::File(path) => Connection::open(path)?,
+            StorageLocation::InMemory => Connection::open_in_memory()?,
+        };
+        migrations::run_migrations(&conn)?;
+        Ok(Self { conn: Mutex::new(conn) })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StorableStory {
+    pub id: u64,
+    pub title: String,
+    pub url: Option<String>,
+    pub score: u64,
+    pub by: String,
+    pub time: u64,
+    pub descendants: u64,
+    pub kids: Vec<u64>,
+    /// Unix timestamp this row was last written, used to judge freshness.
+    pub fetched_at: u64,
+}
+
+impl From<&Story> for StorableStory {
+    fn from(story: &Story) -> Self {
+        Self {
+            id: story.id,
+            title: story.title.clone(),
+            url: story.url.clone(),
+            score: story.score,
+            by: story.by.clone(),
+            time: story.time,
+            descendants: story.descendants,
+            kids: story.kids.clone(),
+            fetched_at: crate::time::now_unix(),
+        }
+    }
+}
+
+impl From<StorableStory> for Story {
+    fn from(row: StorableStory) -> Self {
+        Self {
+            id: row.id,
+            title: row.title,
+            url: row.url,
+            score: row.score,
+            by: row.by,
+            time: row.time,
+            descendants: row.descendants,
+            kids: row.kids,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StorableComment {
+    pub id: u64,
+    pub story_id: u64,
+    pub parent_id: Option<u64>,
+    pub by: String,
+    pub time: u64,
+    pub text: String,
+    pub depth: usize,
+    pub kids: Vec<u64>,
+    /// Unix timestamp this row was last written, used to judge freshness.
+    pub fetched_at: u64,
+}
+
+impl StorableComment {
+    pub fn from_comment(comment: &Comment, story_id: u64, parent_id: Option<u64>) -> Self {
+        Self {
+            id: comment.id,
+            story_id,
+            parent_id,
+            by: comment.by.clone(),
+            time: comment.time,
+            text: comment.text.clone(),
+            depth: comment.depth,
+            kids: comment.kids.clone(),
+            fetched_at: crate::time::now_unix(),
+        }
+    }
+}
+
+impl From<StorableComment> for Comment {
+    fn from(row: StorableComment) -> Self {
+        Self {
+            id: row.id,
+            by: row.by,
+            time: row.time,
+            text: row.text,
+            depth: row.depth,
+            kids: row.kids,
+        }
+    }
+}
+
+/// A single FTS5 match, before [`crate::api::SearchHit`] attaches UI-facing
+/// kind information to `table`.
+#[derive(Debug, Clone)]
+pub struct StorableSearchHit {
+    pub table: String,
+    pub id: u64,
+    pub story_id: u64,
+    pub snippet: String,
+    pub score: f64,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum StorageError {
+    #[error("sqlite error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+
+    #[error("migration {version} failed: {error}")]
+    Migration { version: i64, error: String },
+
+    #[error("migration {version} checksum mismatch: expected {expected}, found {found}")]
+    MigrationChecksumMismatch {
+        version: i64,
+        expected: String,
+        found: String,
+    },
+
+    #[error("migration {version} has no down script to roll back to")]
+    MissingDownMigration { version: i64 },
+}
+
+/// How long a cached row is trusted before `get_fresh_stories`/
+/// `get_fresh_comments[_batch]` treat it as a miss and let `HnClient` refetch
+/// it from the API.
+const FRESHNESS_TTL_SECS: u64 = 15 * 60;
+
+fn encode_kids(kids: &[u64]) -> String {
+    serde_json::to_string(kids).expect("Vec<u64> always serializes")
+}
+
+fn decode_kids(raw: &str) -> Vec<u64> {
+    serde_json::from_str(raw).unwrap_or_default()
+}
+
+impl Cache for Storage {
+    fn get_fresh_stories<'a>(
+        &'a self,
+        ids: &'a [u64],
+    ) -> cache::BoxFuture<'a, Result<std::collections::HashMap<u64, StorableStory>, StorageError>> {
+        Box::pin(async move {
+            if ids.is_empty() {
+                return Ok(std::collections::HashMap::new());
+            }
+
+            let conn = self.conn.lock().unwrap();
+            let cutoff = crate::time::now_unix().saturating_sub(FRESHNESS_TTL_SECS);
+            let placeholders = vec!["?"; ids.len()].join(",");
+            let sql = format!(
+                "SELECT id, title, url, score, by, time, descendants, kids, fetched_at \
+                 FROM stories WHERE id IN ({placeholders}) AND fetched_at > ?"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let query_params: Vec<&dyn rusqlite::ToSql> = ids
+                .iter()
+                .map(|id| id as &dyn rusqlite::ToSql)
+                .chain(std::iter::once(&cutoff as &dyn rusqlite::ToSql))
+                .collect();
+            let rows = stmt.query_map(query_params.as_slice(), |row| {
+                Ok(StorableStory {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    url: row.get(2)?,
+                    score: row.get(3)?,
+                    by: row.get(4)?,
+                    time: row.get(5)?,
+                    descendants: row.get(6)?,
+                    kids: decode_kids(&row.get::<_, String>(7)?),
+                    fetched_at: row.get(8)?,
+                })
+            })?;
+
+            let mut stories = std::collections::HashMap::new();
+            for row in rows {
+                let story = row?;
+                stories.insert(story.id, story);
+            }
+            Ok(stories)
+        })
+    }
+
+    fn save_story<'a>(&'a self, story: &'a StorableStory) -> cache::BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO stories (id, title, url, score, by, time, descendants, kids, fetched_at) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9) \
+                 ON CONFLICT(id) DO UPDATE SET \
+                    title = excluded.title, url = excluded.url, score = excluded.score, \
+                    by = excluded.by, time = excluded.time, descendants = excluded.descendants, \
+                    kids = excluded.kids, fetched_at = excluded.fetched_at",
+                params![
+                    story.id,
+                    story.title,
+                    story.url,
+                    story.score,
+                    story.by,
+                    story.time,
+                    story.descendants,
+                    encode_kids(&story.kids),
+                    story.fetched_at,
+                ],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn get_fresh_comments(
+        &self,
+        story_id: u64,
+    ) -> cache::BoxFuture<'_, Result<Option<Vec<StorableComment>>, StorageError>> {
+        Box::pin(async move {
+            let comments = self.query_fresh_comments(story_id)?;
+            Ok(if comments.is_empty() { None } else { Some(comments) })
+        })
+    }
+
+    fn get_fresh_comments_batch<'a>(
+        &'a self,
+        story_ids: &'a [u64],
+    ) -> cache::BoxFuture<'a, Result<std::collections::HashMap<u64, Vec<StorableComment>>, StorageError>> {
+        Box::pin(async move {
+            let mut by_story: std::collections::HashMap<u64, Vec<StorableComment>> =
+                std::collections::HashMap::new();
+            if story_ids.is_empty() {
+                return Ok(by_story);
+            }
+
+            let conn = self.conn.lock().unwrap();
+            let cutoff = crate::time::now_unix().saturating_sub(FRESHNESS_TTL_SECS);
+            let placeholders = vec!["?"; story_ids.len()].join(",");
+            let sql = format!(
+                "SELECT id, story_id, parent_id, by, time, text, depth, kids, fetched_at \
+                 FROM comments WHERE story_id IN ({placeholders}) AND fetched_at > ?"
+            );
+            let mut stmt = conn.prepare(&sql)?;
+            let query_params: Vec<&dyn rusqlite::ToSql> = story_ids
+                .iter()
+                .map(|id| id as &dyn rusqlite::ToSql)
+                .chain(std::iter::once(&cutoff as &dyn rusqlite::ToSql))
+                .collect();
+            let rows = stmt.query_map(query_params.as_slice(), |row| Self::comment_from_row(row))?;
+            for row in rows {
+                let comment = row?;
+                by_story.entry(comment.story_id).or_default().push(comment);
+            }
+            Ok(by_story)
+        })
+    }
+
+    fn get_comments(&self, story_id: u64) -> cache::BoxFuture<'_, Result<Vec<StorableComment>, StorageError>> {
+        Box::pin(async move { self.query_comments(story_id) })
+    }
+
+    fn save_comments<'a>(
+        &'a self,
+        story_id: u64,
+        comments: &'a [StorableComment],
+    ) -> cache::BoxFuture<'a, Result<(), StorageError>> {
+        Box::pin(async move {
+            let mut conn = self.conn.lock().unwrap();
+            let tx = conn.transaction()?;
+            tx.execute("DELETE FROM comments WHERE story_id = ?1", params![story_id])?;
+            for comment in comments {
+                tx.execute(
+                    "INSERT INTO comments (id, story_id, parent_id, by, time, text, depth, kids, fetched_at) \
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                    params![
+                        comment.id,
+                        comment.story_id,
+                        comment.parent_id,
+                        comment.by,
+                        comment.time,
+                        comment.text,
+                        comment.depth as i64,
+                        encode_kids(&comment.kids),
+                        comment.fetched_at,
+                    ],
+                )?;
+            }
+            tx.commit()?;
+            Ok(())
+        })
+    }
+
+    fn search<'a>(
+        &'a self,
+        query: &'a str,
+        limit: usize,
+    ) -> cache::BoxFuture<'a, Result<Vec<StorableSearchHit>, StorageError>> {
+        Box::pin(async move {
+            // Each term becomes a quoted prefix query (`"term"*`), so a
+            // partial word matches and FTS5 operator characters in the input
+            // are taken literally.
+            let fts_query = query
+                .split_whitespace()
+                .map(|term| format!("\"{}\"*", term.replace('"', "\"\"")))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare(
+                "SELECT 'stories' AS tbl, stories.id, stories.id, \
+                        snippet(stories_fts, 0, '<b>', '</b>', '...', 8), bm25(stories_fts) \
+                 FROM stories_fts JOIN stories ON stories.rowid = stories_fts.rowid \
+                 WHERE stories_fts MATCH ?1 \
+                 UNION ALL \
+                 SELECT 'comments', comments.id, comments.story_id, \
+                        snippet(comments_fts, 0, '<b>', '</b>', '...', 8), bm25(comments_fts) \
+                 FROM comments_fts JOIN comments ON comments.rowid = comments_fts.rowid \
+                 WHERE comments_fts MATCH ?1 \
+                 ORDER BY 5 ASC LIMIT ?2",
+            )?;
+            let rows = stmt.query_map(params![fts_query, limit as i64], |row| {
+                Ok(StorableSearchHit {
+                    table: row.get(0)?,
+                    id: row.get(1)?,
+                    story_id: row.get(2)?,
+                    snippet: row.get(3)?,
+                    score: row.get(4)?,
+                })
+            })?;
+            rows.collect::<Result<Vec<_>, _>>().map_err(StorageError::from)
+        })
+    }
+
+    fn cached_story_ids(&self) -> cache::BoxFuture<'_, Result<Vec<u64>, StorageError>> {
+        Box::pin(async move {
+            let conn = self.conn.lock().unwrap();
+            let mut stmt = conn.prepare("SELECT id FROM stories")?;
+            let rows = stmt.query_map([], |row| row.get(0))?;
+            rows.collect::<Result<Vec<_>, _>>().map_err(StorageError::from)
+        })
+    }
+
+    fn set_last_synced_maxitem(&self, max_item: u64) -> cache::BoxFuture<'_, Result<(), StorageError>> {
+        Box::pin(async move {
+            let conn = self.conn.lock().unwrap();
+            conn.execute(
+                "INSERT INTO sync_state (key, last_synced_maxitem) VALUES (0, ?1) \
+                 ON CONFLICT(key) DO UPDATE SET last_synced_maxitem = excluded.last_synced_maxitem",
+                params![max_item],
+            )?;
+            Ok(())
+        })
+    }
+
+    fn get_last_synced_maxitem(&self) -> cache::BoxFuture<'_, Result<Option<u64>, StorageError>> {
+        Box::pin(async move {
+            let conn = self.conn.lock().unwrap();
+            conn.query_row(
+                "SELECT last_synced_maxitem FROM sync_state WHERE key = 0",
+                [],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(StorageError::from)
+        })
+    }
+}
+
+impl Storage {
+    fn comment_from_row(row: &rusqlite::Row) -> rusqlite::Result<StorableComment> {
+        Ok(StorableComment {
+            id: row.get(0)?,
+            story_id: row.get(1)?,
+            parent_id: row.get(2)?,
+            by: row.get(3)?,
+            time: row.get(4)?,
+            text: row.get(5)?,
+            depth: row.get::<_, i64>(6)? as usize,
+            kids: decode_kids(&row.get::<_, String>(7)?),
+            fetched_at: row.get(8)?,
+        })
+    }
+
+    fn query_comments(&self, story_id: u64) -> Result<Vec<StorableComment>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT id, story_id, parent_id, by, time, text, depth, kids, fetched_at \
+             FROM comments WHERE story_id = ?1",
+        )?;
+        let rows = stmt.query_map(params![story_id], |row| Self::comment_from_row(row))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(StorageError::from)
+    }
+
+    fn query_fresh_comments(&self, story_id: u64) -> Result<Vec<StorableComment>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        let cutoff = crate::time::now_unix().saturating_sub(FRESHNESS_TTL_SECS);
+        let mut stmt = conn.prepare(
+            "SELECT id, story_id, parent_id, by, time, text, depth, kids, fetched_at \
+             FROM comments WHERE story_id = ?1 AND fetched_at > ?2",
+        )?;
+        let rows = stmt.query_map(params![story_id, cutoff], |row| Self::comment_from_row(row))?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(StorageError::from)
+    }
+
+    /// Looks up a single cached row by id, used by tests that only need to
+    /// confirm a write landed rather than round-tripping through `Cache`.
+    #[cfg(test)]
+    fn story_by_id(&self, id: u64) -> Result<Option<StorableStory>, StorageError> {
+        let conn = self.conn.lock().unwrap();
+        conn.query_row(
+            "SELECT id, title, url, score, by, time, descendants, kids, fetched_at \
+             FROM stories WHERE id = ?1",
+            params![id],
+            |row| {
+                Ok(StorableStory {
+                    id: row.get(0)?,
+                    title: row.get(1)?,
+                    url: row.get(2)?,
+                    score: row.get(3)?,
+                    by: row.get(4)?,
+                    time: row.get(5)?,
+                    descendants: row.get(6)?,
+                    kids: decode_kids(&row.get::<_, String>(7)?),
+                    fetched_at: row.get(8)?,
+                })
+            },
+        )
+        .optional()
+        .map_err(StorageError::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_story(id: u64) -> StorableStory {
+        StorableStory {
+            id,
+            title: "Test story".into(),
+            url: Some("https://example.com".into()),
+            score: 42,
+            by: "alice".into(),
+            time: 1_700_000_000,
+            descendants: 3,
+            kids: vec![1, 2, 3],
+            fetched_at: crate::time::now_unix(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_and_fetch_story_round_trips() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        let story = sample_story(1);
+        storage.save_story(&story).await.unwrap();
+
+        let fetched = storage.get_fresh_stories(&[1, 2]).await.unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[&1].title, "Test story");
+    }
+
+    #[tokio::test]
+    async fn test_save_story_upserts_on_conflict() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        storage.save_story(&sample_story(1)).await.unwrap();
+
+        let mut updated = sample_story(1);
+        updated.score = 100;
+        storage.save_story(&updated).await.unwrap();
+
+        let row = storage.story_by_id(1).unwrap().unwrap();
+        assert_eq!(row.score, 100);
+    }
+
+    #[tokio::test]
+    async fn test_get_fresh_comments_is_none_when_uncached() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        assert!(storage.get_fresh_comments(1).await.unwrap().is_none());
+    }
+
+    fn sample_comment(id: u64, story_id: u64, fetched_at: u64) -> StorableComment {
+        StorableComment {
+            id,
+            story_id,
+            parent_id: None,
+            by: "bob".into(),
+            time: 1_700_000_100,
+            text: "first".into(),
+            depth: 0,
+            kids: vec![],
+            fetched_at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_save_comments_then_get_fresh_comments_round_trips() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        storage.save_story(&sample_story(1)).await.unwrap();
+
+        let comments = vec![sample_comment(10, 1, crate::time::now_unix())];
+        storage.save_comments(1, &comments).await.unwrap();
+
+        let fetched = storage.get_fresh_comments(1).await.unwrap().unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert_eq!(fetched[0].by, "bob");
+    }
+
+    #[tokio::test]
+    async fn test_get_fresh_stories_excludes_stale_rows() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        let mut stale = sample_story(1);
+        stale.fetched_at = crate::time::now_unix() - FRESHNESS_TTL_SECS - 1;
+        storage.save_story(&stale).await.unwrap();
+
+        let fetched = storage.get_fresh_stories(&[1]).await.unwrap();
+        assert!(fetched.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_fresh_comments_excludes_stale_rows() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        let stale_fetched_at = crate::time::now_unix() - FRESHNESS_TTL_SECS - 1;
+        storage
+            .save_comments(1, &[sample_comment(10, 1, stale_fetched_at)])
+            .await
+            .unwrap();
+
+        assert!(storage.get_fresh_comments(1).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_fresh_comments_batch_applies_cutoff_per_story() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        let stale_fetched_at = crate::time::now_unix() - FRESHNESS_TTL_SECS - 1;
+        storage
+            .save_comments(1, &[sample_comment(10, 1, crate::time::now_unix())])
+            .await
+            .unwrap();
+        storage
+            .save_comments(2, &[sample_comment(20, 2, stale_fetched_at)])
+            .await
+            .unwrap();
+
+        let fetched = storage.get_fresh_comments_batch(&[1, 2]).await.unwrap();
+        assert_eq!(fetched.len(), 1);
+        assert!(fetched.contains_key(&1));
+        assert!(!fetched.contains_key(&2));
+    }
+
+    #[tokio::test]
+    async fn test_set_last_synced_maxitem_is_idempotent() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        storage.set_last_synced_maxitem(100).await.unwrap();
+        storage.set_last_synced_maxitem(200).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_get_last_synced_maxitem_round_trips() {
+        let storage = Storage::open(StorageLocation::InMemory).unwrap();
+        assert_eq!(storage.get_last_synced_maxitem().await.unwrap(), None);
+
+        storage.set_last_synced_maxitem(100).await.unwrap();
+        assert_eq!(storage.get_last_synced_maxitem().await.unwrap(), Some(100));
+    }
+}
@@ -0,0 +1,160 @@
+//! Test-only helpers for building an [`App`] and [`Comment`] fixtures
+//! without going through the TUI's real startup path (terminal, HTTP
+//! client, on-disk cache).
+
+use crate::api::Comment;
+use crate::app::{App, View};
+use crate::theme::default_for_variant;
+use crate::theme::ThemeVariant;
+
+/// Builds a [`Comment`] field by field; anything left unset gets a
+/// reasonable default (no replies, empty body) so a test only has to name
+/// the fields it actually cares about.
+#[derive(Default)]
+pub struct CommentBuilder {
+    id: u64,
+    text: String,
+    author: String,
+    depth: usize,
+    kids: Vec<u64>,
+    time: u64,
+}
+
+impl CommentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn id(mut self, id: u64) -> Self {
+        self.id = id;
+        self
+    }
+
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    pub fn author(mut self, author: impl Into<String>) -> Self {
+        self.author = author.into();
+        self
+    }
+
+    pub fn depth(mut self, depth: usize) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    pub fn kids(mut self, kids: Vec<u64>) -> Self {
+        self.kids = kids;
+        self
+    }
+
+    pub fn time(mut self, time: u64) -> Self {
+        self.time = time;
+        self
+    }
+
+    pub fn build(self) -> Comment {
+        Comment {
+            id: self.id,
+            by: self.author,
+            text: self.text,
+            depth: self.depth,
+            kids: self.kids,
+            time: self.time,
+        }
+    }
+}
+
+/// A small four-comment thread (one top-level comment with two replies,
+/// one of which has its own reply) used by tests that just need "some
+/// comments" rather than a specific shape.
+pub fn sample_comments() -> Vec<Comment> {
+    vec![
+        CommentBuilder::new()
+            .id(100)
+            .author("alice")
+            .text("Top-level comment")
+            .depth(0)
+            .kids(vec![101, 102])
+            .build(),
+        CommentBuilder::new()
+            .id(101)
+            .author("bob")
+            .text("First reply")
+            .depth(1)
+            .kids(vec![103])
+            .build(),
+        CommentBuilder::new()
+            .id(102)
+            .author("carol")
+            .text("Second reply")
+            .depth(1)
+            .build(),
+        CommentBuilder::new()
+            .id(103)
+            .author("dave")
+            .text("Reply to the first reply")
+            .depth(2)
+            .build(),
+    ]
+}
+
+/// Builds an [`App`] for view-rendering tests: a fixed dark theme, no
+/// network/storage, and whichever state the test opts into.
+pub struct TestAppBuilder {
+    app: App,
+}
+
+impl TestAppBuilder {
+    pub fn new() -> Self {
+        let (action_tx, _action_rx) = tokio::sync::mpsc::unbounded_channel();
+        let theme = default_for_variant(ThemeVariant::Dark);
+        let mut app = App::new_for_test(theme, action_tx);
+        // Tests render a specific state directly; default to "loaded, not
+        // loading, no error" unless a test opts into something else.
+        app.loading = false;
+        Self { app }
+    }
+
+    pub fn view(mut self, view: View) -> Self {
+        self.app.view = view;
+        self
+    }
+
+    pub fn with_comments(mut self, comments: Vec<Comment>) -> Self {
+        self.app.comments = comments;
+        self
+    }
+
+    pub fn expanded(mut self, ids: Vec<u64>) -> Self {
+        self.app.expanded_comments = ids.into_iter().collect();
+        self
+    }
+
+    pub fn loading(mut self) -> Self {
+        self.app.loading = true;
+        self
+    }
+
+    pub fn error(mut self, message: impl Into<String>) -> Self {
+        self.app.error = Some(message.into());
+        self
+    }
+
+    pub fn help_overlay(mut self) -> Self {
+        self.app.help_overlay = true;
+        self
+    }
+
+    pub fn build(self) -> App {
+        self.app
+    }
+}
+
+impl Default for TestAppBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
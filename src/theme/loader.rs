@@ -1,21 +1,312 @@
+use std::collections::HashSet;
 use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
+use ratatui::style::Color;
 
 use super::Theme;
 
-/// Load a theme from a TOML file
+/// Load a theme from a TOML file, resolving any `extends` chain into a
+/// single fully-populated theme.
+///
+/// A theme file may set `extends = "some-base-theme"` and only specify the
+/// fields it wants to override; everything else is inherited from the named
+/// parent (a built-in theme or another file in the same directory).
 pub fn load_theme_file(path: &Path) -> Result<Theme> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("Failed to read theme file: {}", path.display()))?;
 
-    let theme: Theme = toml::from_str(&content)
+    let raw: toml::Value = toml::from_str(&content)
         .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
 
+    // `extends` on a custom theme can name either a built-in or a sibling
+    // file in the same directory, so the parent lookup needs to know where
+    // this file lives.
+    let themes_dir = path.parent();
+    let resolved = resolve_extends(raw, themes_dir, &mut HashSet::new())
+        .with_context(|| format!("Failed to resolve theme inheritance for: {}", path.display()))?;
+
+    validate_hex_colors(&resolved)
+        .with_context(|| format!("Invalid color in theme file: {}", path.display()))?;
+
+    let theme: Theme = resolved
+        .try_into()
+        .with_context(|| format!("Failed to parse theme file: {}", path.display()))?;
+
+    if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+        if theme.name != stem {
+            tracing::warn!(
+                name = %theme.name,
+                file_stem = %stem,
+                path = %path.display(),
+                "theme name does not match its file name"
+            );
+        }
+    }
+
     Ok(theme)
 }
 
+/// Recursively merges a theme's `extends` parent underneath its own table,
+/// so the child's keys take precedence over the parent's while unset fields
+/// fall back to the fully-resolved parent. Cycles are detected via `seen`,
+/// the set of parent names already visited in this chain. `themes_dir` is
+/// the directory of the theme file being loaded, checked for a
+/// `<name>.toml` parent file when the name isn't a built-in, and passed
+/// unchanged down the whole chain; with `None`, only built-in parents
+/// resolve.
+fn resolve_extends(
+    mut value: toml::Value,
+    themes_dir: Option<&Path>,
+    seen: &mut HashSet<String>,
+) -> Result<toml::Value> {
+    let Some(table) = value.as_table_mut() else {
+        return Ok(value);
+    };
+
+    let Some(parent_name) = table
+        .remove("extends")
+        .and_then(|v| v.as_str().map(str::to_owned))
+    else {
+        return Ok(value);
+    };
+
+    if !seen.insert(parent_name.clone()) {
+        bail!("theme inheritance cycle detected at '{parent_name}'");
+    }
+
+    let parent_value = match super::by_name(&parent_name) {
+        Some(parent) => {
+            toml::Value::try_from(parent).context("failed to serialize parent theme")?
+        }
+        None => {
+            let parent_path = themes_dir
+                .with_context(|| format!("extends unknown theme '{parent_name}'"))?
+                .join(format!("{parent_name}.toml"));
+            let parent_content = std::fs::read_to_string(&parent_path).with_context(|| {
+                format!("extends unknown theme '{parent_name}' (also tried {})", parent_path.display())
+            })?;
+            toml::from_str(&parent_content)
+                .with_context(|| format!("Failed to parse theme file: {}", parent_path.display()))?
+        }
+    };
+    let parent_value = resolve_extends(parent_value, themes_dir, seen)?;
+
+    Ok(merge_tables(parent_value, value))
+}
+
+/// Walks every string value in a parsed theme table and checks that any
+/// `#`-prefixed one is a valid hex color, so a typo like `#ff88` (missing a
+/// digit) is rejected at load time instead of silently passing through as
+/// an unparseable color further down the line.
+fn validate_hex_colors(value: &toml::Value) -> Result<()> {
+    match value {
+        toml::Value::String(s) if s.starts_with('#') => {
+            if parse_hex_color(s).is_none() {
+                bail!("'{s}' is not a valid #rrggbb or #rgb color");
+            }
+            Ok(())
+        }
+        toml::Value::Table(table) => {
+            for v in table.values() {
+                validate_hex_colors(v)?;
+            }
+            Ok(())
+        }
+        toml::Value::Array(items) => {
+            for v in items {
+                validate_hex_colors(v)?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Overlays `overrides` onto `base`, recursing into nested tables (like
+/// `[meta]`) instead of replacing them wholesale.
+fn merge_tables(base: toml::Value, overrides: toml::Value) -> toml::Value {
+    match (base, overrides) {
+        (toml::Value::Table(mut base), toml::Value::Table(overrides)) => {
+            for (key, value) in overrides {
+                let merged = match base.remove(&key) {
+                    Some(base_value) => merge_tables(base_value, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            toml::Value::Table(base)
+        }
+        (_, overrides) => overrides,
+    }
+}
+
+/// Parses a `#rrggbb` or `#rgb` hex string into an RGB color.
+pub fn parse_hex_color(s: &str) -> Option<Color> {
+    let hex = s.strip_prefix('#')?;
+    let (r, g, b) = match hex.len() {
+        6 => (
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        ),
+        3 => {
+            let double = |c: &str| u8::from_str_radix(c, 16).ok().map(|v| v * 17);
+            (
+                double(&hex[0..1])?,
+                double(&hex[1..2])?,
+                double(&hex[2..3])?,
+            )
+        }
+        _ => return None,
+    };
+    Some(Color::Rgb(r, g, b))
+}
+
 /// Serialize a theme to TOML format
 pub fn theme_to_toml(theme: &Theme) -> Result<String> {
     toml::to_string_pretty(theme).context("Failed to serialize theme to TOML")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_color_six_digit() {
+        assert_eq!(parse_hex_color("#ff8800"), Some(Color::Rgb(0xff, 0x88, 0x00)));
+    }
+
+    #[test]
+    fn test_parse_hex_color_three_digit() {
+        assert_eq!(parse_hex_color("#f80"), Some(Color::Rgb(0xff, 0x88, 0x00)));
+    }
+
+    #[test]
+    fn test_parse_hex_color_rejects_missing_hash() {
+        assert_eq!(parse_hex_color("ff8800"), None);
+    }
+
+    #[test]
+    fn test_parse_hex_color_rejects_invalid_length() {
+        assert_eq!(parse_hex_color("#ff88"), None);
+    }
+
+    #[test]
+    fn test_merge_tables_overrides_take_precedence() {
+        let base: toml::Value = toml::from_str("title = \"base\"\nborder = \"gray\"").unwrap();
+        let overrides: toml::Value = toml::from_str("title = \"child\"").unwrap();
+        let merged = merge_tables(base, overrides);
+        assert_eq!(merged.get("title").unwrap().as_str(), Some("child"));
+        assert_eq!(merged.get("border").unwrap().as_str(), Some("gray"));
+    }
+
+    #[test]
+    fn test_merge_tables_merges_nested_tables() {
+        let base: toml::Value = toml::from_str("[meta]\nvariant = \"dark\"\ndescription = \"base\"").unwrap();
+        let overrides: toml::Value = toml::from_str("[meta]\ndescription = \"child\"").unwrap();
+        let merged = merge_tables(base, overrides);
+        let meta = merged.get("meta").unwrap();
+        assert_eq!(meta.get("variant").unwrap().as_str(), Some("dark"));
+        assert_eq!(meta.get("description").unwrap().as_str(), Some("child"));
+    }
+
+    #[test]
+    fn test_validate_hex_colors_accepts_valid_colors() {
+        let value: toml::Value = toml::from_str("border = \"#ff8800\"\n[meta]\naccent = \"#f80\"").unwrap();
+        assert!(validate_hex_colors(&value).is_ok());
+    }
+
+    #[test]
+    fn test_validate_hex_colors_rejects_malformed_color() {
+        let value: toml::Value = toml::from_str("border = \"#ff88\"").unwrap();
+        let err = validate_hex_colors(&value).unwrap_err();
+        assert!(err.to_string().contains("#ff88"));
+    }
+
+    /// A directory under the OS temp dir unique to this test, cleaned up on
+    /// drop, so tests that need real files on disk don't collide with each
+    /// other or leave litter behind.
+    struct TempThemesDir(std::path::PathBuf);
+
+    impl TempThemesDir {
+        fn new(name: &str) -> Self {
+            let dir = std::env::temp_dir().join(format!("hn-theme-loader-test-{name}"));
+            let _ = std::fs::remove_dir_all(&dir);
+            std::fs::create_dir_all(&dir).unwrap();
+            Self(dir)
+        }
+
+        fn write(&self, file_name: &str, contents: &str) -> std::path::PathBuf {
+            let path = self.0.join(file_name);
+            std::fs::write(&path, contents).unwrap();
+            path
+        }
+    }
+
+    impl Drop for TempThemesDir {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn test_resolve_extends_against_sibling_file() {
+        let dir = TempThemesDir::new("sibling-extends");
+        dir.write(
+            "base-custom.toml",
+            "name = \"base-custom\"\nborder = \"#111111\"\nwarning = \"#222222\"\n",
+        );
+        let child_path = dir.write(
+            "child.toml",
+            "name = \"child\"\nextends = \"base-custom\"\nwarning = \"#333333\"\n",
+        );
+
+        let content = std::fs::read_to_string(&child_path).unwrap();
+        let raw: toml::Value = toml::from_str(&content).unwrap();
+        let resolved = resolve_extends(raw, child_path.parent(), &mut HashSet::new()).unwrap();
+
+        // Inherited from the sibling file...
+        assert_eq!(resolved.get("border").unwrap().as_str(), Some("#111111"));
+        // ...but the child's own value still wins.
+        assert_eq!(resolved.get("warning").unwrap().as_str(), Some("#333333"));
+    }
+
+    #[test]
+    fn test_resolve_extends_reports_missing_parent() {
+        let dir = TempThemesDir::new("missing-parent");
+        let raw: toml::Value =
+            toml::from_str("name = \"child\"\nextends = \"does-not-exist\"\n").unwrap();
+
+        let err = resolve_extends(raw, Some(dir.0.as_path()), &mut HashSet::new()).unwrap_err();
+        assert!(err.to_string().contains("does-not-exist"));
+    }
+
+    #[test]
+    fn test_load_theme_file_extends_builtin_theme() {
+        let dir = TempThemesDir::new("extends-builtin");
+        let path = dir.write(
+            "child.toml",
+            "name = \"child\"\nextends = \"dark\"\nwarning = \"#ff0000\"\n",
+        );
+
+        let theme = load_theme_file(&path).unwrap();
+
+        // Overridden field wins...
+        assert_eq!(theme.warning, "#ff0000");
+        // ...everything else is inherited from the "dark" built-in.
+        assert_eq!(theme.border, super::super::by_name("dark").unwrap().border);
+    }
+
+    #[test]
+    fn test_load_theme_file_rejects_malformed_color_after_extends() {
+        let dir = TempThemesDir::new("extends-bad-color");
+        let path = dir.write(
+            "child.toml",
+            "name = \"child\"\nextends = \"dark\"\nwarning = \"#ff00\"\n",
+        );
+
+        let err = load_theme_file(&path).unwrap_err();
+        assert!(err.to_string().contains("#ff00"));
+    }
+}
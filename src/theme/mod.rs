@@ -0,0 +1,222 @@
+//! Theme definitions: the declarative, serializable [`Theme`] loaded from a
+//! built-in registry or a TOML file (see [`loader`]), and [`ResolvedTheme`],
+//! the same data with every color already parsed so renderers never touch
+//! TOML or hex strings on the hot path.
+
+mod detect;
+pub mod loader;
+
+use ratatui::style::{Color, Style};
+use serde::{Deserialize, Serialize};
+
+pub use detect::detect_terminal_theme;
+pub use loader::load_theme_file;
+
+/// A theme as it's declared: built in, or loaded from a TOML file. Colors
+/// are hex strings rather than [`Color`] here so a theme file round-trips
+/// through `toml`/`serde_json` untouched; [`ResolvedTheme`] is what
+/// renderers actually read from.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Theme {
+    pub name: String,
+    #[serde(default)]
+    pub meta: ThemeMeta,
+
+    pub story_title: String,
+    pub warning: String,
+    pub border: String,
+    pub error: String,
+    pub foreground_dim: String,
+    pub selection_bg: String,
+    pub comment_text: String,
+    pub status_bar_bg: String,
+    pub status_bar_fg: String,
+    pub spinner: String,
+    pub link: String,
+    pub blockquote_gutter: String,
+    pub code_text: String,
+
+    /// Hex colors cycled through per comment-tree depth when
+    /// `rainbow_depth_colors` is set; falls back to a single repeated
+    /// `spinner`-colored guide when empty.
+    #[serde(default)]
+    pub depth_colors: Vec<String>,
+    /// When `true`, each depth of a comment thread's tree guides gets its
+    /// own color from `depth_colors`, cycling once it runs out, so a thread
+    /// reads as a consistent colored rainbow back to its root. When `false`
+    /// (the default), every depth uses the same accent color.
+    #[serde(default)]
+    pub rainbow_depth_colors: bool,
+}
+
+/// Metadata about a theme that doesn't affect rendering: which background
+/// it's designed for, and a human-readable blurb shown by `hn theme list -v`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ThemeMeta {
+    pub variant: ThemeVariant,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeVariant {
+    Dark,
+    Light,
+}
+
+impl Default for ThemeVariant {
+    fn default() -> Self {
+        ThemeVariant::Dark
+    }
+}
+
+/// A [`Theme`] with every color already parsed, so renderers never parse
+/// hex strings themselves. A theme file's malformed `#` colors are rejected
+/// at load time by `loader::validate_hex_colors`; any value that still
+/// doesn't parse as hex resolves to [`Color::Reset`].
+#[derive(Debug, Clone)]
+pub struct ResolvedTheme {
+    pub name: String,
+
+    pub story_title: Color,
+    pub warning: Color,
+    pub border: Color,
+    pub error: Color,
+    pub foreground_dim: Color,
+    pub selection_bg: Color,
+    pub comment_text: Color,
+    pub status_bar_bg: Color,
+    pub status_bar_fg: Color,
+    pub spinner: Color,
+    pub link: Color,
+    pub blockquote_gutter: Color,
+    pub code_text: Color,
+
+    depth_colors: Vec<Color>,
+    pub rainbow_depth_colors: bool,
+}
+
+impl From<Theme> for ResolvedTheme {
+    fn from(theme: Theme) -> Self {
+        let color = |s: &str| loader::parse_hex_color(s).unwrap_or(Color::Reset);
+        ResolvedTheme {
+            name: theme.name,
+            story_title: color(&theme.story_title),
+            warning: color(&theme.warning),
+            border: color(&theme.border),
+            error: color(&theme.error),
+            foreground_dim: color(&theme.foreground_dim),
+            selection_bg: color(&theme.selection_bg),
+            comment_text: color(&theme.comment_text),
+            status_bar_bg: color(&theme.status_bar_bg),
+            status_bar_fg: color(&theme.status_bar_fg),
+            spinner: color(&theme.spinner),
+            link: color(&theme.link),
+            blockquote_gutter: color(&theme.blockquote_gutter),
+            code_text: color(&theme.code_text),
+            depth_colors: theme.depth_colors.iter().map(|s| color(s)).collect(),
+            rainbow_depth_colors: theme.rainbow_depth_colors,
+        }
+    }
+}
+
+impl ResolvedTheme {
+    /// Color for a comment at the given tree depth. When `rainbow_depth_colors`
+    /// is off, or the theme declares no `depth_colors` at all, every depth
+    /// gets the same accent color (`spinner`) rather than cycling.
+    pub fn depth_color(&self, depth: usize) -> Color {
+        if !self.rainbow_depth_colors || self.depth_colors.is_empty() {
+            return self.spinner;
+        }
+        self.depth_colors[depth % self.depth_colors.len()]
+    }
+
+    /// Style for de-emphasized text, e.g. a help overlay's key labels.
+    pub fn dim_style(&self) -> Style {
+        Style::default().fg(self.foreground_dim)
+    }
+
+    /// Style for titles and other text that should stand out.
+    pub fn story_title_style(&self) -> Style {
+        Style::default().fg(self.story_title)
+    }
+
+    /// Style for box-drawing borders.
+    pub fn border_style(&self) -> Style {
+        Style::default().fg(self.border)
+    }
+}
+
+fn dark_theme() -> Theme {
+    Theme {
+        name: "dark".into(),
+        meta: ThemeMeta {
+            variant: ThemeVariant::Dark,
+            description: Some("Default dark theme".into()),
+        },
+        story_title: "#ffffff".into(),
+        warning: "#e5c07b".into(),
+        border: "#3e4452".into(),
+        error: "#e06c75".into(),
+        foreground_dim: "#5c6370".into(),
+        selection_bg: "#3e4452".into(),
+        comment_text: "#abb2bf".into(),
+        status_bar_bg: "#3e4452".into(),
+        status_bar_fg: "#ffffff".into(),
+        spinner: "#61afef".into(),
+        link: "#61afef".into(),
+        blockquote_gutter: "#5c6370".into(),
+        code_text: "#98c379".into(),
+        depth_colors: ["#e06c75", "#e5c07b", "#98c379", "#61afef", "#c678dd", "#56b6c2"]
+            .map(String::from)
+            .to_vec(),
+        rainbow_depth_colors: false,
+    }
+}
+
+fn light_theme() -> Theme {
+    Theme {
+        name: "light".into(),
+        meta: ThemeMeta {
+            variant: ThemeVariant::Light,
+            description: Some("Default light theme".into()),
+        },
+        story_title: "#000000".into(),
+        warning: "#b58900".into(),
+        border: "#d3d3d3".into(),
+        error: "#dc322f".into(),
+        foreground_dim: "#657b83".into(),
+        selection_bg: "#eee8d5".into(),
+        comment_text: "#073642".into(),
+        status_bar_bg: "#eee8d5".into(),
+        status_bar_fg: "#073642".into(),
+        spinner: "#268bd2".into(),
+        link: "#268bd2".into(),
+        blockquote_gutter: "#657b83".into(),
+        code_text: "#859900".into(),
+        depth_colors: ["#dc322f", "#b58900", "#859900", "#268bd2", "#6c71c4", "#2aa198"]
+            .map(String::from)
+            .to_vec(),
+        rainbow_depth_colors: false,
+    }
+}
+
+/// Every built-in theme, in display order for `hn theme list`.
+pub fn all_themes() -> Vec<Theme> {
+    vec![dark_theme(), light_theme()]
+}
+
+/// Looks up a built-in theme by name (not a custom theme file; that's
+/// [`load_theme_file`]).
+pub fn by_name(name: &str) -> Option<Theme> {
+    all_themes().into_iter().find(|t| t.name == name)
+}
+
+/// The built-in theme for a detected or requested [`ThemeVariant`], already
+/// resolved since this is the common "just start the TUI" path.
+pub fn default_for_variant(variant: ThemeVariant) -> ResolvedTheme {
+    match variant {
+        ThemeVariant::Dark => dark_theme().into(),
+        ThemeVariant::Light => light_theme().into(),
+    }
+}
@@ -8,19 +8,33 @@ use futures::StreamExt;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::io::{self, Stdout};
 use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio::time::interval;
 
 use crate::event::Event;
 
 pub type Tui = Terminal<CrosstermBackend<Stdout>>;
 
-pub fn init() -> Result<Tui> {
+/// Restores the terminal when dropped, so it comes back to normal even if
+/// the main loop returns early via `?` or unwinds from a panic. A
+/// `std::process::exit` skips it, so the signal handler restores on its own.
+pub struct TuiGuard;
+
+impl Drop for TuiGuard {
+    fn drop(&mut self) {
+        if let Err(e) = restore() {
+            eprintln!("Failed to restore terminal: {e}");
+        }
+    }
+}
+
+pub fn init() -> Result<(Tui, TuiGuard)> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend)?;
-    Ok(terminal)
+    Ok((terminal, TuiGuard))
 }
 
 pub fn restore() -> Result<()> {
@@ -29,27 +43,78 @@ pub fn restore() -> Result<()> {
     Ok(())
 }
 
+/// Installs a panic hook that restores the terminal before handing off to
+/// the previous hook, so a panic mid-render doesn't leave the terminal
+/// stuck in raw mode / alternate screen with the panic message unreadable.
+pub fn install_panic_hook() {
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |panic_info| {
+        let _ = restore();
+        original_hook(panic_info);
+    }));
+}
+
+/// Spawns a task that restores the terminal and exits on SIGINT/SIGTERM,
+/// so Ctrl-C (or a supervisor's SIGTERM) doesn't leave the terminal
+/// corrupted.
+pub fn spawn_signal_handler() {
+    tokio::spawn(async {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        let Ok(mut sigint) = signal(SignalKind::interrupt()) else {
+            return;
+        };
+        let Ok(mut sigterm) = signal(SignalKind::terminate()) else {
+            return;
+        };
+
+        tokio::select! {
+            _ = sigint.recv() => {}
+            _ = sigterm.recv() => {}
+        }
+
+        let _ = restore();
+        std::process::exit(130);
+    });
+}
+
 pub struct EventHandler {
     event_stream: EventStream,
-    tick_rate: Duration,
+    tick_interval: tokio::time::Interval,
+    action_tx: mpsc::UnboundedSender<Event>,
+    action_rx: mpsc::UnboundedReceiver<Event>,
 }
 
 impl EventHandler {
     pub fn new(tick_rate_ms: u64) -> Self {
+        let (action_tx, action_rx) = mpsc::unbounded_channel();
         Self {
             event_stream: EventStream::new(),
-            tick_rate: Duration::from_millis(tick_rate_ms),
+            tick_interval: interval(Duration::from_millis(tick_rate_ms)),
+            action_tx,
+            action_rx,
         }
     }
 
-    pub async fn next(&mut self) -> Result<Event> {
-        let mut tick_interval = interval(self.tick_rate);
+    /// Returns a cloneable handle that background tasks (HTTP fetches
+    /// spawned from key/mouse handlers) can use to post an `Event::Data`
+    /// back into the main loop once they complete, without blocking the
+    /// render thread while the request is in flight.
+    pub fn action_sender(&self) -> mpsc::UnboundedSender<Event> {
+        self.action_tx.clone()
+    }
 
+    pub async fn next(&mut self) -> Result<Event> {
         loop {
             tokio::select! {
-                _ = tick_interval.tick() => {
+                _ = self.tick_interval.tick() => {
                     return Ok(Event::Tick);
                 }
+                action = self.action_rx.recv() => {
+                    if let Some(action) = action {
+                        return Ok(action);
+                    }
+                }
                 event = self.event_stream.next() => {
                     if let Some(Ok(event)) = event {
                         match event {
@@ -58,8 +123,8 @@ impl EventHandler {
                                     return Ok(Event::Key(key));
                                 }
                             }
-                            CrosstermEvent::Mouse(_) => {
-                                return Ok(Event::Mouse);
+                            CrosstermEvent::Mouse(mouse) => {
+                                return Ok(Event::Mouse(mouse));
                             }
                             CrosstermEvent::Resize(_, _) => {
                                 return Ok(Event::Resize);
@@ -5,11 +5,12 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
     Frame,
 };
-use textwrap;
 
 use crate::api::Comment;
 use crate::app::{App, View};
 use crate::theme::ResolvedTheme;
+use crate::views::html::render_comment_html;
+use crate::views::tree::{build_empty_line_prefix, build_meta_tree_prefix, build_text_prefix, compute_tree_context, DepthColors};
 
 /// Render the comments view
 pub fn render(frame: &mut Frame, app: &App, area: Rect) {
@@ -49,6 +50,7 @@ fn render_comment_list(frame: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(theme.warning))
             .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.border)).title("Comments"));
         frame.render_widget(loading, area);
+        clear_comment_rows(app);
         return;
     }
 
@@ -57,6 +59,7 @@ fn render_comment_list(frame: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(theme.error))
             .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.border)).title("Error"));
         frame.render_widget(error, area);
+        clear_comment_rows(app);
         return;
     }
 
@@ -65,6 +68,7 @@ fn render_comment_list(frame: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(theme.foreground_dim))
             .block(Block::default().borders(Borders::ALL).border_style(Style::default().fg(theme.border)).title("Comments"));
         frame.render_widget(empty, area);
+        clear_comment_rows(app);
         return;
     }
 
@@ -73,14 +77,18 @@ fn render_comment_list(frame: &mut Frame, app: &App, area: Rect) {
 
     // Get only visible comments based on expansion state
     let visible_indices = app.visible_comment_indices();
-    let items: Vec<ListItem> = visible_indices
+    let tree_context = compute_tree_context(&app.comments, &visible_indices);
+    let items_with_heights: Vec<(ListItem, usize)> = visible_indices
         .iter()
-        .map(|&i| {
+        .zip(&tree_context)
+        .map(|(&i, has_more_at_depth)| {
             let comment = &app.comments[i];
             let is_expanded = app.expanded_comments.contains(&comment.id);
-            comment_to_list_item(comment, content_width, is_expanded, theme)
+            comment_to_list_item(comment, has_more_at_depth, content_width, is_expanded, theme)
         })
         .collect();
+    let item_heights: Vec<usize> = items_with_heights.iter().map(|(_, h)| *h).collect();
+    let items: Vec<ListItem> = items_with_heights.into_iter().map(|(item, _)| item).collect();
 
     let list = List::new(items)
         .block(
@@ -95,34 +103,81 @@ fn render_comment_list(frame: &mut Frame, app: &App, area: Rect) {
     let mut state = ListState::default();
     state.select(Some(app.selected_index));
 
-    // Center the selected item (scrolloff behavior)
-    // Estimate ~4 lines per comment on average for visible item calculation
-    let visible_count = visible_indices.len();
-    let visible_items = (area.height.saturating_sub(2) / 4).max(1) as usize;
+    // Seed the offset with a scrolloff estimate (average line height) so the
+    // selection starts out roughly centered; `List`'s own layout then takes
+    // the real per-item heights into account and, if needed, auto-scrolls
+    // further to keep the selection on screen.
+    let visible_count = item_heights.len();
+    let avg_height = if item_heights.is_empty() {
+        1
+    } else {
+        (item_heights.iter().sum::<usize>() / item_heights.len()).max(1)
+    };
+    let visible_items = (area.height.saturating_sub(2) as usize / avg_height).max(1);
     let half = visible_items / 2;
     let max_offset = visible_count.saturating_sub(visible_items);
     let offset = app.selected_index.saturating_sub(half).min(max_offset);
     *state.offset_mut() = offset;
 
     frame.render_stateful_widget(list, area, &mut state);
+
+    // `state.offset()` is where `List` actually settled after accounting for
+    // real item heights and keeping the selection visible, so use that -
+    // not our pre-render estimate - to place the recorded rows.
+    record_comment_rows(app, area, &item_heights, state.offset());
 }
 
-fn comment_to_list_item(comment: &Comment, max_width: usize, is_expanded: bool, theme: &ResolvedTheme) -> ListItem<'static> {
+fn clear_comment_rows(app: &App) {
+    let mut mouse_regions = app.mouse_regions.borrow_mut();
+    mouse_regions.rows.clear();
+    mouse_regions.rows_offset = 0;
+}
+
+/// Records each visible row's `Rect` for mouse hit-testing by walking
+/// `item_heights` (each comment's real rendered height, from
+/// [`comment_to_list_item`]) from `offset` and stacking rows of that exact
+/// height, rather than assuming a uniform row size.
+fn record_comment_rows(app: &App, area: Rect, item_heights: &[usize], offset: usize) {
+    let inner = area.inner(ratatui::layout::Margin::new(1, 1));
+    let bottom = inner.y + inner.height;
+
+    let mut regions = Vec::new();
+    let mut y = inner.y;
+    for &height in item_heights.iter().skip(offset) {
+        if y >= bottom {
+            break;
+        }
+        let row_height = (height as u16).min(bottom - y);
+        regions.push(Rect::new(inner.x, y, inner.width, row_height));
+        y += height as u16;
+    }
+
+    let mut mouse_regions = app.mouse_regions.borrow_mut();
+    mouse_regions.rows = regions;
+    mouse_regions.rows_offset = offset;
+}
+
+fn comment_to_list_item(
+    comment: &Comment,
+    has_more_at_depth: &[bool],
+    max_width: usize,
+    is_expanded: bool,
+    theme: &ResolvedTheme,
+) -> (ListItem<'static>, usize) {
     let color = theme.depth_color(comment.depth);
-    let indent_width = comment.depth * 2;
-    let indent = " ".repeat(indent_width);
     let has_children = !comment.kids.is_empty();
-
-    // Depth marker with color
-    let depth_marker = if comment.depth > 0 {
-        Span::styled(
-            format!("{}├─ ", &indent[..indent_width.saturating_sub(3)]),
-            Style::default().fg(color),
-        )
-    } else {
-        Span::raw("")
+    let colors = DepthColors {
+        depth_color: &|d| theme.depth_color(d),
+        // Themes opt out of per-depth rainbow coloring via
+        // `rainbow_depth_colors = false`; `depth_color` still drives the
+        // single accent color used for everything else in that case.
+        rainbow: theme.rainbow_depth_colors,
     };
 
+    let meta_prefix = build_meta_tree_prefix(comment.depth, has_more_at_depth, &colors);
+    let text_prefix = build_text_prefix(comment.depth, has_more_at_depth, has_children, &colors);
+    let empty_line_prefix = build_empty_line_prefix(comment.depth, has_more_at_depth, has_children, &colors);
+
     // Collapse/expand indicator (fixed width for alignment)
     let expand_indicator = if has_children {
         if is_expanded {
@@ -148,58 +203,44 @@ fn comment_to_list_item(comment: &Comment, max_width: usize, is_expanded: bool,
     };
 
     // Author line with colored marker
-    let mut meta_spans = vec![
-        depth_marker,
-        expand_indicator,
-        Span::styled(
-            comment.by.clone(),
-            Style::default().fg(color).add_modifier(Modifier::BOLD),
-        ),
-        Span::styled(" · ", Style::default().fg(theme.foreground_dim)),
-        Span::styled(format_time(comment.time), Style::default().fg(theme.foreground_dim)),
-    ];
+    let mut meta_spans = meta_prefix;
+    meta_spans.push(expand_indicator);
+    meta_spans.push(Span::styled(
+        comment.by.clone(),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ));
+    meta_spans.push(Span::styled(" · ", Style::default().fg(theme.foreground_dim)));
+    meta_spans.push(Span::styled(format_time(comment.time), Style::default().fg(theme.foreground_dim)));
     meta_spans.extend(child_info);
     let meta_line = Line::from(meta_spans);
 
     // If collapsed with children, show only meta line
     if has_children && !is_expanded {
-        return ListItem::new(vec![meta_line, Line::from("")]);
+        let lines = vec![meta_line, Line::from(empty_line_prefix)];
+        let height = lines.len();
+        return (ListItem::new(lines), height);
     }
 
-    // Process and wrap comment text
-    let text = strip_html(&comment.text);
-    let text_indent = indent.clone() + "      "; // Extra indent for text body (accounts for expand indicator)
-    let available_width = max_width.saturating_sub(text_indent.len()).max(20);
+    // Render the comment body, keeping paragraph/code-block/list structure intact
+    let text_prefix_width: usize = text_prefix.iter().map(|s| s.content.chars().count()).sum();
+    let available_width = max_width.saturating_sub(text_prefix_width).max(20);
 
-    // Wrap text to fit available width
-    let wrapped_lines = wrap_text(&text, available_width);
+    let body_lines = render_comment_html(comment.id, &comment.text, available_width, theme);
 
     // Build text lines with proper indentation
     let mut lines = vec![meta_line];
 
-    for wrapped_line in wrapped_lines {
-        lines.push(Line::from(vec![
-            Span::styled(text_indent.clone(), Style::default().fg(theme.foreground_dim)),
-            Span::styled(wrapped_line, Style::default().fg(theme.comment_text)),
-        ]));
+    for body_line in body_lines {
+        let mut spans = text_prefix.clone();
+        spans.extend(body_line.spans);
+        lines.push(Line::from(spans));
     }
 
     // Add empty line for spacing between comments
-    lines.push(Line::from(""));
-
-    ListItem::new(lines)
-}
-
-/// Wrap text to specified width, preserving words
-fn wrap_text(text: &str, width: usize) -> Vec<String> {
-    if text.is_empty() {
-        return vec![];
-    }
+    lines.push(Line::from(empty_line_prefix));
 
-    textwrap::wrap(text, width)
-        .into_iter()
-        .map(|cow| cow.into_owned())
-        .collect()
+    let height = lines.len();
+    (ListItem::new(lines), height)
 }
 
 fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
@@ -263,57 +304,6 @@ fn format_time(timestamp: u64) -> String {
     }
 }
 
-fn strip_html(html: &str) -> String {
-    // Convert HTML to readable text
-    html.replace("<p>", "\n\n")
-        .replace("</p>", "")
-        .replace("<br>", "\n")
-        .replace("<br/>", "\n")
-        .replace("<br />", "\n")
-        .replace("<i>", "_")
-        .replace("</i>", "_")
-        .replace("<b>", "*")
-        .replace("</b>", "*")
-        .replace("<code>", "`")
-        .replace("</code>", "`")
-        .replace("<pre>", "\n```\n")
-        .replace("</pre>", "\n```\n")
-        .replace("&gt;", ">")
-        .replace("&lt;", "<")
-        .replace("&amp;", "&")
-        .replace("&quot;", "\"")
-        .replace("&#x27;", "'")
-        .replace("&#39;", "'")
-        .replace("&#x2F;", "/")
-        // Strip links but keep text
-        .split("<a ")
-        .enumerate()
-        .map(|(i, part)| {
-            if i == 0 {
-                part.to_string()
-            } else {
-                // Find the link text between > and </a>
-                if let Some(start) = part.find('>') {
-                    if let Some(end) = part.find("</a>") {
-                        let link_text = &part[start + 1..end];
-                        let rest = &part[end + 4..];
-                        return format!("{}{}", link_text, rest);
-                    }
-                }
-                part.to_string()
-            }
-        })
-        .collect::<String>()
-        // Clean up whitespace
-        .lines()
-        .map(|l| l.trim())
-        .collect::<Vec<_>>()
-        .join(" ")
-        .split_whitespace()
-        .collect::<Vec<_>>()
-        .join(" ")
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -321,38 +311,6 @@ mod tests {
     use crate::test_utils::{sample_comments, CommentBuilder, TestAppBuilder};
     use crate::views::tests::render_to_string;
 
-    #[test]
-    fn test_strip_html_basic_tags() {
-        assert_eq!(strip_html("<p>Hello</p><p>World</p>"), "Hello World");
-        assert_eq!(strip_html("Line1<br>Line2"), "Line1 Line2");
-    }
-
-    #[test]
-    fn test_strip_html_formatting() {
-        assert_eq!(strip_html("<i>italic</i>"), "_italic_");
-        assert_eq!(strip_html("<b>bold</b>"), "*bold*");
-        assert_eq!(strip_html("<code>code</code>"), "`code`");
-    }
-
-    #[test]
-    fn test_strip_html_entities() {
-        assert_eq!(strip_html("&lt;tag&gt;"), "<tag>");
-        assert_eq!(strip_html("&amp;&quot;&#x27;"), "&\"'");
-        assert_eq!(strip_html("path&#x2F;to&#x2F;file"), "path/to/file");
-    }
-
-    #[test]
-    fn test_strip_html_links() {
-        let html = r#"Check <a href="https://example.com">this link</a> out"#;
-        assert_eq!(strip_html(html), "Check this link out");
-    }
-
-    #[test]
-    fn test_strip_html_collapses_whitespace() {
-        assert_eq!(strip_html("  too   many    spaces  "), "too many spaces");
-        assert_eq!(strip_html("<p>  \n\n  </p>text"), "text");
-    }
-
     #[test]
     fn test_comments_view_renders_thread() {
         let app = TestAppBuilder::new()
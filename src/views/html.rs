@@ -0,0 +1,610 @@
+//! HTML-to-text renderer for Hacker News comment bodies.
+//!
+//! HN comments arrive as a small, loosely-formed subset of HTML. Rather than
+//! collapsing everything into one space-joined `String` (which destroys
+//! paragraph breaks and mangles code blocks), this module tokenizes the
+//! markup and walks it as a tree: block elements (`<p>`, `<pre>`,
+//! `<blockquote>`, `<li>`) emit hard line breaks, while inline elements
+//! (`<i>`, `<b>`, `<code>`, `<a>`) map to styled [`Span`]s. The result is a
+//! `Vec<Line<'static>>` already wrapped to the caller's width, so callers can
+//! render it directly without losing paragraph or code-block structure.
+
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+
+use crate::highlight::highlight_pre_block;
+use crate::theme::ResolvedTheme;
+
+/// A parsed HTML token. Tags carry only the bits the renderer cares about.
+#[derive(Debug, PartialEq)]
+enum Token {
+    StartTag { name: String, href: Option<String> },
+    EndTag { name: String },
+    Text(String),
+}
+
+/// Splits a comment's raw HTML into a flat stream of tokens.
+///
+/// This is a best-effort tokenizer, not a full HTML5 parser: it understands
+/// tags, attributes and entities well enough to survive the markup HN
+/// actually emits, without choking on the unbalanced tags that a naive
+/// `find`/`split` chain would corrupt.
+fn tokenize(html: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut chars = html.char_indices().peekable();
+    let mut text = String::new();
+
+    while let Some(&(start, c)) = chars.peek() {
+        if c != '<' {
+            text.push(c);
+            chars.next();
+            continue;
+        }
+
+        // Find the matching '>' for this tag; if there isn't one, treat the
+        // rest of the input as text rather than panicking on malformed markup.
+        let Some(end) = html[start..].find('>') else {
+            text.push_str(&html[start..]);
+            break;
+        };
+        let tag_src = &html[start + 1..start + end];
+        if !text.is_empty() {
+            tokens.push(Token::Text(decode_entities(&text)));
+            text.clear();
+        }
+
+        if let Some(name) = tag_src.strip_prefix('/') {
+            tokens.push(Token::EndTag {
+                name: name.trim().to_ascii_lowercase(),
+            });
+        } else {
+            let tag_src = tag_src.trim_end_matches('/'); // self-closing, e.g. <br/>
+            let mut parts = tag_src.splitn(2, char::is_whitespace);
+            let name = parts.next().unwrap_or("").trim().to_ascii_lowercase();
+            let href = parts.next().and_then(find_href_attr);
+            tokens.push(Token::StartTag { name, href });
+        }
+
+        for _ in 0..=end {
+            chars.next();
+        }
+    }
+
+    if !text.is_empty() {
+        tokens.push(Token::Text(decode_entities(&text)));
+    }
+    tokens
+}
+
+/// Pulls the value of an `href="..."` attribute out of a tag's attribute string.
+fn find_href_attr(attrs: &str) -> Option<String> {
+    let idx = attrs.to_ascii_lowercase().find("href")?;
+    let rest = &attrs[idx + "href".len()..];
+    let rest = rest.trim_start();
+    let rest = rest.strip_prefix('=')?.trim_start();
+    let quote = rest.chars().next()?;
+    if quote != '"' && quote != '\'' {
+        return None;
+    }
+    let rest = &rest[1..];
+    let end = rest.find(quote)?;
+    Some(decode_entities(&rest[..end]))
+}
+
+/// Decodes the handful of HTML entities HN's API actually emits.
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let Some(semi) = rest.find(';').filter(|&i| i <= 10) else {
+            out.push('&');
+            rest = &rest[1..];
+            continue;
+        };
+        let entity = &rest[1..semi];
+        let decoded = match entity {
+            "gt" => Some('>'),
+            "lt" => Some('<'),
+            "amp" => Some('&'),
+            "quot" => Some('"'),
+            "#x27" | "#39" | "apos" => Some('\''),
+            "#x2F" | "#47" => Some('/'),
+            _ => entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+                .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                .or_else(|| entity.strip_prefix('#').and_then(|dec| dec.parse().ok()))
+                .and_then(char::from_u32),
+        };
+        match decoded {
+            Some(c) => {
+                out.push(c);
+                rest = &rest[semi + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Inline emphasis state, tracked as a stack so nested `<b><i>` survives.
+#[derive(Clone, Copy, PartialEq)]
+enum Emphasis {
+    Italic,
+    Bold,
+    Code,
+    Link,
+}
+
+/// List context for `<ul>`/`<ol>`, tracked as a stack to support nesting.
+enum ListKind {
+    Unordered,
+    Ordered(usize),
+}
+
+/// A single atom of inline text carrying the style it should render with.
+/// `is_space` atoms are collapsible break points for word-wrapping.
+struct Atom {
+    text: String,
+    style: Style,
+    is_space: bool,
+}
+
+/// Accumulates the inline content of the block currently being assembled
+/// (a paragraph, list item, or blockquote line) before it gets word-wrapped.
+#[derive(Default)]
+struct Block {
+    atoms: Vec<Atom>,
+}
+
+impl Block {
+    fn is_empty(&self) -> bool {
+        self.atoms.iter().all(|a| a.is_space)
+    }
+
+    /// Appends a text node, turning each whitespace character (newlines
+    /// included) into a breakable space atom; `wrap_block` collapses a run
+    /// of them into a single space.
+    fn push_text(&mut self, text: &str, style: Style) {
+        let mut buf = String::new();
+        for part in text.split_inclusive(char::is_whitespace) {
+            let (word, ws) = match part.find(char::is_whitespace) {
+                Some(i) => part.split_at(i),
+                None => (part, ""),
+            };
+            buf.push_str(word);
+            if !ws.is_empty() {
+                if !buf.is_empty() {
+                    self.atoms.push(Atom { text: std::mem::take(&mut buf), style, is_space: false });
+                }
+                self.atoms.push(Atom { text: " ".to_string(), style, is_space: true });
+            }
+        }
+        if !buf.is_empty() {
+            self.atoms.push(Atom { text: buf, style, is_space: false });
+        }
+    }
+}
+
+/// Word-wraps a block's atoms to `width`, producing one `Line` per wrapped
+/// row, starting with `first_prefix` and with `cont_prefix` on every
+/// continuation row after it.
+///
+/// Adjacent atoms that share a style are merged into a single `Span`, so a
+/// run of plain words becomes one span rather than one per word.
+fn wrap_block(
+    block: Block,
+    width: usize,
+    first_prefix: Vec<Span<'static>>,
+    cont_prefix: Vec<Span<'static>>,
+) -> Vec<Line<'static>> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let prefix_width = |prefix: &[Span]| prefix.iter().map(|s| s.content.chars().count()).sum::<usize>();
+
+    let mut spans: Vec<Span<'static>> = first_prefix.clone();
+    let mut line_width = prefix_width(&first_prefix);
+    let mut run = String::new();
+    let mut run_style: Option<Style> = None;
+    let mut pending_space = false;
+    let mut any_word = false;
+
+    fn flush_run(spans: &mut Vec<Span<'static>>, run: &mut String, run_style: &mut Option<Style>) {
+        if !run.is_empty() {
+            spans.push(Span::styled(std::mem::take(run), run_style.take().unwrap_or_default()));
+        }
+    }
+
+    for atom in block.atoms {
+        if atom.is_space {
+            pending_space = any_word;
+            continue;
+        }
+
+        let word_width = atom.text.chars().count();
+        let extra = usize::from(pending_space);
+        if any_word && line_width + extra + word_width > width {
+            flush_run(&mut spans, &mut run, &mut run_style);
+            lines.push(Line::from(std::mem::take(&mut spans)));
+            spans = cont_prefix.clone();
+            line_width = prefix_width(&cont_prefix);
+            any_word = false;
+            pending_space = false;
+        }
+
+        if pending_space && any_word {
+            if run_style == Some(atom.style) {
+                run.push(' ');
+            } else {
+                flush_run(&mut spans, &mut run, &mut run_style);
+                run.push(' ');
+                run_style = Some(atom.style);
+            }
+            line_width += 1;
+        }
+
+        if run_style == Some(atom.style) {
+            run.push_str(&atom.text);
+        } else {
+            flush_run(&mut spans, &mut run, &mut run_style);
+            run = atom.text.clone();
+            run_style = Some(atom.style);
+        }
+        line_width += word_width;
+        any_word = true;
+        pending_space = false;
+    }
+
+    flush_run(&mut spans, &mut run, &mut run_style);
+    if any_word || lines.is_empty() {
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Tracks accumulated render state while walking the token stream.
+struct Renderer<'a> {
+    comment_id: u64,
+    theme: &'a ResolvedTheme,
+    max_width: usize,
+    lines: Vec<Line<'static>>,
+    emphasis: Vec<Emphasis>,
+    blockquote_depth: usize,
+    list_stack: Vec<ListKind>,
+    pre_depth: usize,
+    pre_buffer: String,
+    /// How many `<pre>` blocks this comment has closed so far, used to key
+    /// the syntax-highlight cache alongside `comment_id`.
+    pre_block_index: usize,
+    block: Block,
+    list_marker: Option<String>,
+    /// Hrefs of currently-open `<a>` tags, so `</a>` knows what it closed.
+    open_links: Vec<Option<String>>,
+    /// Footnote targets in first-seen order, deduplicated by URL.
+    footnotes: Vec<String>,
+}
+
+impl<'a> Renderer<'a> {
+    fn new(comment_id: u64, theme: &'a ResolvedTheme, max_width: usize) -> Self {
+        Self {
+            comment_id,
+            theme,
+            max_width,
+            lines: Vec::new(),
+            emphasis: Vec::new(),
+            blockquote_depth: 0,
+            list_stack: Vec::new(),
+            pre_depth: 0,
+            pre_buffer: String::new(),
+            pre_block_index: 0,
+            block: Block::default(),
+            list_marker: None,
+            open_links: Vec::new(),
+            footnotes: Vec::new(),
+        }
+    }
+
+    fn inline_style(&self) -> Style {
+        let mut style = Style::default().fg(self.theme.comment_text);
+        for e in &self.emphasis {
+            style = match e {
+                Emphasis::Italic => style.add_modifier(Modifier::ITALIC),
+                Emphasis::Bold => style.add_modifier(Modifier::BOLD),
+                Emphasis::Code => style.fg(self.theme.code_text),
+                Emphasis::Link => style.fg(self.theme.link).add_modifier(Modifier::UNDERLINED),
+            };
+        }
+        style
+    }
+
+    /// Registers a footnote target, reusing the existing number if this URL
+    /// was already referenced elsewhere in the comment.
+    fn footnote_index(&mut self, href: &str) -> usize {
+        if let Some(i) = self.footnotes.iter().position(|f| f == href) {
+            return i;
+        }
+        self.footnotes.push(href.to_string());
+        self.footnotes.len() - 1
+    }
+
+    fn flush_block(&mut self) {
+        if self.block.is_empty() {
+            self.block = Block::default();
+            self.list_marker = None;
+            return;
+        }
+
+        let gutter_span = || Span::styled("▏ ", Style::default().fg(self.theme.blockquote_gutter));
+        let mut first_prefix = Vec::new();
+        let mut cont_prefix = Vec::new();
+        for _ in 0..self.blockquote_depth {
+            first_prefix.push(gutter_span());
+            cont_prefix.push(gutter_span());
+        }
+
+        let marker_width = if let Some(marker) = &self.list_marker {
+            first_prefix.push(Span::styled(marker.clone(), Style::default().fg(self.theme.foreground_dim)));
+            cont_prefix.push(Span::raw(" ".repeat(marker.chars().count())));
+            marker.chars().count()
+        } else {
+            0
+        };
+
+        let width = self
+            .max_width
+            .saturating_sub(self.blockquote_depth * 2 + marker_width)
+            .max(10);
+
+        let block = std::mem::take(&mut self.block);
+        self.lines.extend(wrap_block(block, width, first_prefix, cont_prefix));
+        self.list_marker = None;
+    }
+
+    fn handle(&mut self, token: Token) {
+        match token {
+            Token::Text(text) => {
+                if self.pre_depth > 0 {
+                    self.pre_buffer.push_str(&text);
+                } else {
+                    let style = self.inline_style();
+                    self.block.push_text(&text, style);
+                }
+            }
+            Token::StartTag { name, href } => match name.as_str() {
+                "p" => {
+                    self.flush_block();
+                }
+                "br" => self.flush_block(),
+                "pre" => {
+                    self.flush_block();
+                    self.pre_depth += 1;
+                }
+                "blockquote" => {
+                    self.flush_block();
+                    self.blockquote_depth += 1;
+                }
+                "ul" => {
+                    self.flush_block();
+                    self.list_stack.push(ListKind::Unordered);
+                }
+                "ol" => {
+                    self.flush_block();
+                    self.list_stack.push(ListKind::Ordered(0));
+                }
+                "li" => {
+                    self.flush_block();
+                    self.list_marker = Some(match self.list_stack.last_mut() {
+                        Some(ListKind::Ordered(n)) => {
+                            *n += 1;
+                            format!("{}. ", n)
+                        }
+                        _ => "• ".to_string(),
+                    });
+                }
+                "i" | "em" => self.emphasis.push(Emphasis::Italic),
+                "b" | "strong" => self.emphasis.push(Emphasis::Bold),
+                "code" => self.emphasis.push(Emphasis::Code),
+                "a" => {
+                    self.open_links.push(href);
+                    self.emphasis.push(Emphasis::Link);
+                }
+                _ => {}
+            },
+            Token::EndTag { name } => match name.as_str() {
+                "p" => {
+                    self.flush_block();
+                    self.lines.push(Line::default());
+                }
+                "pre" => {
+                    self.pre_depth = self.pre_depth.saturating_sub(1);
+                    if self.pre_depth == 0 {
+                        let code = self.pre_buffer.trim_matches('\n');
+                        self.lines.extend(highlight_pre_block(
+                            self.comment_id,
+                            self.pre_block_index,
+                            code,
+                            self.theme,
+                        ));
+                        self.pre_block_index += 1;
+                        self.pre_buffer.clear();
+                    }
+                }
+                "blockquote" => {
+                    self.flush_block();
+                    self.blockquote_depth = self.blockquote_depth.saturating_sub(1);
+                }
+                "ul" | "ol" => {
+                    self.flush_block();
+                    self.list_stack.pop();
+                }
+                "li" => self.flush_block(),
+                "i" | "em" | "b" | "strong" | "code" => {
+                    self.emphasis.pop();
+                }
+                "a" => {
+                    self.emphasis.pop();
+                    if let Some(Some(href)) = self.open_links.pop() {
+                        let idx = self.footnote_index(&href);
+                        let style = self.inline_style();
+                        self.block.atoms.push(Atom {
+                            text: format!("[{}]", idx + 1),
+                            style: style.fg(self.theme.foreground_dim),
+                            is_space: false,
+                        });
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    fn finish(mut self) -> Vec<Line<'static>> {
+        self.flush_block();
+        // Trim a single trailing blank line left by a closing `</p>`.
+        if self.lines.last().is_some_and(|l| l.spans.is_empty()) {
+            self.lines.pop();
+        }
+
+        if !self.footnotes.is_empty() {
+            self.lines.push(Line::default());
+            let dim = Style::default().fg(self.theme.foreground_dim);
+            for (i, href) in self.footnotes.iter().enumerate() {
+                self.lines.push(Line::from(Span::styled(
+                    format!("[{}] {}", i + 1, href),
+                    dim,
+                )));
+            }
+        }
+
+        self.lines
+    }
+}
+
+/// Renders comment HTML into styled, pre-wrapped lines, with links rendered
+/// as numbered footnotes: the visible text stays inline (e.g. `this link[1]`)
+/// and a dim "references" block listing each URL follows the comment body.
+/// Identical URLs are collapsed to the same footnote number.
+///
+/// `max_width` bounds paragraph, list and blockquote text (their prefixes
+/// are subtracted from it automatically); `<pre>` content is always emitted
+/// verbatim with no wrapping, syntax-highlighted via [`crate::highlight`].
+///
+/// `comment_id` keys the highlight cache for this comment's code blocks, so
+/// pass the same id across re-renders of the same comment (e.g. on scroll).
+pub fn render_comment_html(
+    comment_id: u64,
+    html: &str,
+    max_width: usize,
+    theme: &ResolvedTheme,
+) -> Vec<Line<'static>> {
+    let mut renderer = Renderer::new(comment_id, theme, max_width);
+    for token in tokenize(html) {
+        renderer.handle(token);
+    }
+    renderer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::theme;
+
+    fn theme() -> ResolvedTheme {
+        theme::default_for_variant(theme::ThemeVariant::Dark)
+    }
+
+    fn render(html: &str) -> String {
+        render_comment_html(0, html, 80, &theme())
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn paragraphs_become_blank_line_separated_blocks() {
+        let out = render("<p>First</p><p>Second</p>");
+        assert_eq!(out, "First\n\nSecond");
+    }
+
+    #[test]
+    fn pre_blocks_are_verbatim() {
+        let out = render("<pre><code>fn main() {\n    1\n}</code></pre>");
+        assert!(out.contains("fn main() {"));
+        assert!(out.contains("    1"));
+    }
+
+    #[test]
+    fn blockquote_gets_gutter_prefix() {
+        let out = render("<blockquote>quoted text</blockquote>");
+        assert!(out.contains("▏ quoted text"));
+    }
+
+    #[test]
+    fn list_items_get_markers() {
+        let out = render("<ul><li>one</li><li>two</li></ul>");
+        assert!(out.contains("• one"));
+        assert!(out.contains("• two"));
+    }
+
+    #[test]
+    fn ordered_list_items_are_numbered() {
+        let out = render("<ol><li>first</li><li>second</li></ol>");
+        assert!(out.contains("1. first"));
+        assert!(out.contains("2. second"));
+    }
+
+    #[test]
+    fn entities_are_decoded() {
+        assert_eq!(render("&lt;tag&gt;"), "<tag>");
+        assert_eq!(render("&amp;&quot;&#x27;"), "&\"'");
+        assert_eq!(render("path&#x2F;to&#x2F;file"), "path/to/file");
+    }
+
+    #[test]
+    fn link_text_survives() {
+        let out = render(r#"Check <a href="https://example.com">this link</a> out"#);
+        assert!(out.starts_with("Check this link[1] out"));
+    }
+
+    #[test]
+    fn links_become_numbered_footnotes() {
+        let out = render(
+            r#"<a href="https://a.example">A</a> and <a href="https://b.example">B</a>"#,
+        );
+        assert!(out.contains("A[1] and B[2]"));
+        assert!(out.contains("[1] https://a.example"));
+        assert!(out.contains("[2] https://b.example"));
+    }
+
+    #[test]
+    fn duplicate_link_urls_share_one_footnote() {
+        let out = render(
+            r#"<a href="https://a.example">A</a> and <a href="https://a.example">also A</a>"#,
+        );
+        assert!(out.contains("A[1] and also A[1]"));
+        assert_eq!(out.matches("https://a.example").count(), 1); // deduped to a single reference
+    }
+
+    #[test]
+    fn paragraph_internal_whitespace_collapses() {
+        let out = render("<p>  too   many    spaces  </p>");
+        assert_eq!(out, "too many spaces");
+    }
+
+    #[test]
+    fn long_line_wraps_on_width() {
+        let out = render_comment_html(0, "<p>one two three four five</p>", 11, &theme());
+        assert_eq!(out.len(), 3);
+    }
+}
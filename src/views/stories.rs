@@ -24,24 +24,31 @@ pub fn render(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_feed_tabs(frame: &mut Frame, app: &App, area: Rect) {
-    let tabs: Vec<Span> = Feed::all()
-        .iter()
-        .enumerate()
-        .flat_map(|(i, feed)| {
-            let style = if *feed == app.feed {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
-            };
-            vec![
-                Span::styled(format!("[{}]", i + 1), Style::default().fg(Color::DarkGray)),
-                Span::styled(feed.label(), style),
-                Span::raw("  "),
-            ]
-        })
-        .collect();
+    let mut tabs = Vec::new();
+    let mut feed_tabs = Vec::new();
+    let mut x = area.x;
+
+    for (i, feed) in Feed::all().iter().enumerate() {
+        let style = if *feed == app.feed {
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+
+        let number = format!("[{}]", i + 1);
+        let label = feed.label();
+        let width = (number.chars().count() + label.chars().count() + 2) as u16;
+        feed_tabs.push((Rect::new(x, area.y, width, 1), i));
+        x += width;
+
+        tabs.push(Span::styled(number, Style::default().fg(Color::DarkGray)));
+        tabs.push(Span::styled(label, style));
+        tabs.push(Span::raw("  "));
+    }
+
+    app.mouse_regions.borrow_mut().feed_tabs = feed_tabs;
 
     let tabs_line = Line::from(tabs);
     frame.render_widget(Paragraph::new(tabs_line), area);
@@ -53,6 +60,7 @@ fn render_story_list(frame: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(Color::Yellow))
             .block(Block::default().borders(Borders::ALL).title("Stories"));
         frame.render_widget(loading, area);
+        app.mouse_regions.borrow_mut().rows.clear();
         return;
     }
 
@@ -61,6 +69,7 @@ fn render_story_list(frame: &mut Frame, app: &App, area: Rect) {
             .style(Style::default().fg(Color::Red))
             .block(Block::default().borders(Borders::ALL).title("Error"));
         frame.render_widget(error, area);
+        app.mouse_regions.borrow_mut().rows.clear();
         return;
     }
 
@@ -86,6 +95,28 @@ fn render_story_list(frame: &mut Frame, app: &App, area: Rect) {
     let mut state = ListState::default();
     state.select(Some(app.selected_index));
     frame.render_stateful_widget(list, area, &mut state);
+
+    // `render_stateful_widget` may have auto-scrolled `state` to keep the
+    // selection visible, so `state.offset()` (not our pre-render guess) is
+    // the first story index actually on screen.
+    record_story_rows(app, area, app.stories.len(), state.offset());
+}
+
+/// Records each visible row's `Rect` for mouse hit-testing, using the real
+/// scroll offset the list widget settled on and each story's fixed
+/// `ROW_HEIGHT` of 2 lines (title + meta, per [`story_to_list_item`]).
+fn record_story_rows(app: &App, area: Rect, story_count: usize, offset: usize) {
+    const ROW_HEIGHT: u16 = 2;
+    let inner = area.inner(ratatui::layout::Margin::new(1, 1));
+    let rows = (inner.height / ROW_HEIGHT) as usize;
+
+    let regions = (0..rows.min(story_count.saturating_sub(offset)))
+        .map(|i| Rect::new(inner.x, inner.y + i as u16 * ROW_HEIGHT, inner.width, ROW_HEIGHT))
+        .collect();
+
+    let mut mouse_regions = app.mouse_regions.borrow_mut();
+    mouse_regions.rows = regions;
+    mouse_regions.rows_offset = offset;
 }
 
 fn story_to_list_item(story: &Story, rank: usize) -> ListItem<'static> {
@@ -2,7 +2,10 @@
 //!
 //! Builds ASCII tree prefixes (│, ├─, └─) for nested comment display.
 
-use ratatui::{style::Style, text::Span};
+use ratatui::{
+    style::{Color, Style},
+    text::Span,
+};
 
 use crate::api::Comment;
 
@@ -36,40 +39,65 @@ pub fn compute_tree_context(comments: &[Comment], visible_indices: &[usize]) ->
         .collect()
 }
 
+/// Per-depth color lookup for tree guides.
+///
+/// When `rainbow` is `false`, every segment of a prefix is painted with the
+/// color for the comment's own depth (the old single-color behavior). When
+/// `true`, each ancestor segment is painted with the color of the depth it
+/// actually represents, so a reply's guides read as a consistent colored
+/// thread back to its root.
+pub struct DepthColors<'a> {
+    pub depth_color: &'a dyn Fn(usize) -> Color,
+    pub rainbow: bool,
+}
+
+impl DepthColors<'_> {
+    fn at(&self, depth: usize, own_depth: usize) -> Color {
+        if self.rainbow {
+            (self.depth_color)(depth)
+        } else {
+            (self.depth_color)(own_depth)
+        }
+    }
+}
+
 /// Build the tree prefix for a comment's meta line (author, time).
 ///
-/// Returns a styled span with the appropriate tree characters:
+/// Returns one styled [`Span`] per indentation segment, so each ancestor
+/// continuation (`│`) can be colored by the depth it represents:
 /// - `├─` if there are more siblings at this depth
 /// - `└─` if this is the last sibling at this depth
 /// - `│` for ancestor continuation
 pub fn build_meta_tree_prefix(
     depth: usize,
     has_more_at_depth: &[bool],
-    color: ratatui::style::Color,
-) -> Span<'static> {
+    colors: &DepthColors,
+) -> Vec<Span<'static>> {
     if depth == 0 {
-        return Span::raw("");
+        return vec![Span::raw("")];
     }
 
-    let mut prefix = String::new();
+    let mut spans = Vec::with_capacity(depth);
 
     // Add ancestor continuation (│ or spaces) for depths 1 to depth-1
     for d in 1..depth {
-        if has_more_at_depth.get(d).copied().unwrap_or(false) {
-            prefix.push_str(" │  ");
+        let segment = if has_more_at_depth.get(d).copied().unwrap_or(false) {
+            " │  "
         } else {
-            prefix.push_str("    ");
-        }
+            "    "
+        };
+        spans.push(Span::styled(segment, Style::default().fg(colors.at(d, depth))));
     }
 
     // Add connector for current depth
-    if has_more_at_depth.get(depth).copied().unwrap_or(false) {
-        prefix.push_str(" ├─ ");
+    let connector = if has_more_at_depth.get(depth).copied().unwrap_or(false) {
+        " ├─ "
     } else {
-        prefix.push_str(" └─ ");
-    }
+        " └─ "
+    };
+    spans.push(Span::styled(connector, Style::default().fg(colors.at(depth, depth))));
 
-    Span::styled(prefix, Style::default().fg(color))
+    spans
 }
 
 /// Build the tree prefix for comment text lines.
@@ -80,27 +108,28 @@ pub fn build_text_prefix(
     depth: usize,
     has_more_at_depth: &[bool],
     has_children: bool,
-    color: ratatui::style::Color,
-) -> Span<'static> {
-    let mut prefix = String::new();
+    colors: &DepthColors,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::with_capacity(depth + 1);
 
     // Add ancestor continuation for depths 1 to depth
     for d in 1..=depth {
-        if has_more_at_depth.get(d).copied().unwrap_or(false) {
-            prefix.push_str(" │  ");
+        let segment = if has_more_at_depth.get(d).copied().unwrap_or(false) {
+            " │  "
         } else {
-            prefix.push_str("    ");
-        }
+            "    "
+        };
+        spans.push(Span::styled(segment, Style::default().fg(colors.at(d, depth))));
     }
 
     // Add own tree line if has visible children
     if has_children {
-        prefix.push_str(" │  ");
+        spans.push(Span::styled(" │  ", Style::default().fg(colors.at(depth, depth))));
     } else {
-        prefix.push_str("    ");
+        spans.push(Span::raw("    "));
     }
 
-    Span::styled(prefix, Style::default().fg(color))
+    spans
 }
 
 /// Build the tree prefix for the empty line after a comment.
@@ -110,25 +139,26 @@ pub fn build_empty_line_prefix(
     depth: usize,
     has_more_at_depth: &[bool],
     has_children: bool,
-    color: ratatui::style::Color,
-) -> Span<'static> {
-    let mut prefix = String::new();
+    colors: &DepthColors,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::with_capacity(depth + 1);
 
     // Add continuation markers for depths 1 to depth
     for d in 1..=depth {
-        if has_more_at_depth.get(d).copied().unwrap_or(false) {
-            prefix.push_str(" │  ");
+        let segment = if has_more_at_depth.get(d).copied().unwrap_or(false) {
+            " │  "
         } else {
-            prefix.push_str("    ");
-        }
+            "    "
+        };
+        spans.push(Span::styled(segment, Style::default().fg(colors.at(d, depth))));
     }
 
     // Add own tree line if has visible children
     if has_children {
-        prefix.push_str(" │");
+        spans.push(Span::styled(" │", Style::default().fg(colors.at(depth, depth))));
     }
 
-    Span::styled(prefix, Style::default().fg(color))
+    spans
 }
 
 #[cfg(test)]
@@ -174,33 +204,119 @@ mod tests {
         assert_eq!(context[2], vec![false]); // Last at depth 0
     }
 
+    /// A thread where the final branch runs straight down from the root to
+    /// its deepest reply, never sharing a parent with anything that comes
+    /// after it. Every comment on that branch should read as the last
+    /// sibling at every depth it passes through (`has_more_at_depth` all
+    /// `false`), not just at its own depth.
+    ///
+    ///   1
+    ///   ├── 2
+    ///   └── 3
+    ///       └── 4
+    ///           └── 5
+    #[test]
+    fn test_compute_tree_context_deepest_branch_is_last_sibling_at_every_depth() {
+        let comments = vec![
+            CommentBuilder::new().id(1).depth(0).kids(vec![2, 3]).build(),
+            CommentBuilder::new().id(2).depth(1).build(),
+            CommentBuilder::new().id(3).depth(1).kids(vec![4]).build(),
+            CommentBuilder::new().id(4).depth(2).kids(vec![5]).build(),
+            CommentBuilder::new().id(5).depth(3).build(),
+        ];
+        let visible = vec![0, 1, 2, 3, 4];
+        let context = compute_tree_context(&comments, &visible);
+
+        assert_eq!(context[0], vec![false]); // 1: only root
+        assert_eq!(context[1], vec![false, true]); // 2: sibling 3 still to come
+        assert_eq!(context[2], vec![false, false]); // 3: last sibling at depth 1...
+        assert_eq!(context[3], vec![false, false, false]); // 4: ...and so is its child...
+        assert_eq!(context[4], vec![false, false, false, false]); // 5: ...all the way down
+    }
+
+    fn uniform_colors() -> DepthColors<'static> {
+        DepthColors {
+            depth_color: &|_| Color::White,
+            rainbow: false,
+        }
+    }
+
+    fn joined(spans: &[Span<'static>]) -> String {
+        spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
     #[test]
     fn test_build_meta_tree_prefix_root() {
-        let prefix = build_meta_tree_prefix(0, &[false], ratatui::style::Color::White);
-        assert_eq!(prefix.content, "");
+        let prefix = build_meta_tree_prefix(0, &[false], &uniform_colors());
+        assert_eq!(joined(&prefix), "");
     }
 
     #[test]
     fn test_build_meta_tree_prefix_with_sibling() {
-        let prefix = build_meta_tree_prefix(1, &[false, true], ratatui::style::Color::White);
-        assert_eq!(prefix.content, " ├─ ");
+        let prefix = build_meta_tree_prefix(1, &[false, true], &uniform_colors());
+        assert_eq!(joined(&prefix), " ├─ ");
     }
 
     #[test]
     fn test_build_meta_tree_prefix_last_sibling() {
-        let prefix = build_meta_tree_prefix(1, &[false, false], ratatui::style::Color::White);
-        assert_eq!(prefix.content, " └─ ");
+        let prefix = build_meta_tree_prefix(1, &[false, false], &uniform_colors());
+        assert_eq!(joined(&prefix), " └─ ");
     }
 
     #[test]
     fn test_build_text_prefix_with_children() {
-        let prefix = build_text_prefix(0, &[false], true, ratatui::style::Color::White);
-        assert_eq!(prefix.content, " │  ");
+        let prefix = build_text_prefix(0, &[false], true, &uniform_colors());
+        assert_eq!(joined(&prefix), " │  ");
     }
 
     #[test]
     fn test_build_text_prefix_no_children() {
-        let prefix = build_text_prefix(0, &[false], false, ratatui::style::Color::White);
-        assert_eq!(prefix.content, "    ");
+        let prefix = build_text_prefix(0, &[false], false, &uniform_colors());
+        assert_eq!(joined(&prefix), "    ");
+    }
+
+    #[test]
+    fn test_build_prefixes_for_deepest_last_sibling_branch() {
+        // Same thread as test_compute_tree_context_deepest_branch_is_last_sibling_at_every_depth:
+        // 5 sits at the bottom of a branch that was the last sibling at every
+        // depth above it, so every ancestor segment should render as blank
+        // space rather than a `│` continuation.
+        let has_more_at_depth = [false, false, false, false];
+
+        let meta_prefix = build_meta_tree_prefix(3, &has_more_at_depth, &uniform_colors());
+        assert_eq!(joined(&meta_prefix), "    ".repeat(2) + " └─ ");
+
+        let text_prefix = build_text_prefix(3, &has_more_at_depth, false, &uniform_colors());
+        assert_eq!(joined(&text_prefix), "    ".repeat(4));
+    }
+
+    #[test]
+    fn test_build_meta_tree_prefix_rainbow_colors_each_segment() {
+        let colors = DepthColors {
+            depth_color: &|d| match d {
+                1 => Color::Red,
+                2 => Color::Blue,
+                _ => Color::White,
+            },
+            rainbow: true,
+        };
+        let prefix = build_meta_tree_prefix(2, &[false, true, false], &colors);
+        assert_eq!(prefix[0].style.fg, Some(Color::Red));
+        assert_eq!(prefix[1].style.fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_build_meta_tree_prefix_non_rainbow_uses_own_depth_color() {
+        let colors = DepthColors {
+            depth_color: &|d| match d {
+                1 => Color::Red,
+                2 => Color::Blue,
+                _ => Color::White,
+            },
+            rainbow: false,
+        };
+        let prefix = build_meta_tree_prefix(2, &[false, true, false], &colors);
+        assert_eq!(prefix[0].style.fg, Some(Color::Blue));
+        assert_eq!(prefix[1].style.fg, Some(Color::Blue));
     }
 }
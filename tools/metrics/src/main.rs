@@ -1,14 +1,30 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use syn::spanned::Spanned;
 use syn::visit::Visit;
-use syn::{File, ImplItem, ItemFn, ItemImpl};
+use syn::{BinOp, Block, Expr, ExprLit, File, ImplItem, ItemFn, ItemImpl, Lit};
 use walkdir::WalkDir;
 
 #[derive(Default)]
 struct Metrics {
     impl_methods: HashMap<String, Vec<MethodInfo>>,
     functions: Vec<FunctionInfo>,
+    lint_findings: Vec<LintFinding>,
+    /// `(caller, callee)` pairs gathered from `Expr::Call`/`Expr::MethodCall`
+    /// sites, keyed by bare identifier. No type resolution is attempted, so
+    /// two functions with the same name are treated as one node; that's an
+    /// acceptable trade-off for a heuristic in-repo coupling report.
+    call_edges: Vec<(String, String)>,
+}
+
+/// A `x.len() == 0`-shaped comparison caught by [`len_comparison_rewrite`],
+/// reported so the author can swap it for the equivalent `is_empty()` call.
+struct LintFinding {
+    file: String,
+    line: usize,
+    pattern: String,
+    suggestion: String,
 }
 
 #[derive(Clone)]
@@ -18,6 +34,7 @@ struct MethodInfo {
     file: String,
     line: usize,
     lines: usize,
+    complexity: usize,
 }
 
 #[derive(Clone)]
@@ -27,14 +44,207 @@ struct FunctionInfo {
     line: usize,
     lines: usize,
     params: usize,
+    complexity: usize,
+}
+
+/// Counts decision points in a function body: `1 + branches`, following the
+/// usual cyclomatic-complexity formula. Doesn't descend into nested `fn`
+/// items, since those get their own count when the outer visitor reaches
+/// them separately.
+struct ComplexityVisitor {
+    complexity: usize,
+}
+
+impl<'ast> Visit<'ast> for ComplexityVisitor {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        match node {
+            Expr::If(_) | Expr::While(_) | Expr::ForLoop(_) | Expr::Loop(_) | Expr::Try(_) => {
+                self.complexity += 1;
+            }
+            Expr::Binary(bin) if matches!(bin.op, BinOp::And(_) | BinOp::Or(_)) => {
+                self.complexity += 1;
+            }
+            Expr::Match(m) => {
+                for (i, arm) in m.arms.iter().enumerate() {
+                    if i > 0 {
+                        self.complexity += 1;
+                    }
+                    if arm.guard.is_some() {
+                        self.complexity += 1;
+                    }
+                }
+            }
+            _ => {}
+        }
+        syn::visit::visit_expr(self, node);
+    }
+
+    fn visit_item_fn(&mut self, _node: &'ast ItemFn) {
+        // Nested fn items are walked separately by MetricsVisitor.
+    }
+}
+
+fn cyclomatic_complexity(block: &Block) -> usize {
+    let mut visitor = ComplexityVisitor { complexity: 1 };
+    visitor.visit_block(block);
+    visitor.complexity
+}
+
+/// Renders an expression well enough for a lint message, e.g. `self.items`
+/// or `entries.iter().collect()` (method-call arguments and turbofish are
+/// dropped). Falls back to a placeholder for
+/// shapes this lightweight tool doesn't bother spelling out; it only needs
+/// to be readable, not a faithful re-print of the source.
+fn describe_expr(expr: &Expr) -> String {
+    match expr {
+        Expr::Path(p) => p
+            .path
+            .segments
+            .iter()
+            .map(|s| s.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::"),
+        Expr::Field(f) => {
+            let member = match &f.member {
+                syn::Member::Named(ident) => ident.to_string(),
+                syn::Member::Unnamed(index) => index.index.to_string(),
+            };
+            format!("{}.{}", describe_expr(&f.base), member)
+        }
+        Expr::MethodCall(m) => format!("{}.{}()", describe_expr(&m.receiver), m.method),
+        Expr::Paren(p) => describe_expr(&p.expr),
+        Expr::Reference(r) => format!("&{}", describe_expr(&r.expr)),
+        Expr::Unary(u) => format!("{}{}", unary_op_str(&u.op), describe_expr(&u.expr)),
+        _ => "<expr>".into(),
+    }
+}
+
+fn unary_op_str(op: &syn::UnOp) -> &'static str {
+    match op {
+        syn::UnOp::Deref(_) => "*",
+        syn::UnOp::Not(_) => "!",
+        syn::UnOp::Neg(_) => "-",
+        _ => "",
+    }
+}
+
+/// Returns the receiver of a no-arg `.len()` call, e.g. `Some(self.items)`
+/// for `self.items.len()`.
+fn len_call_receiver(expr: &Expr) -> Option<&Expr> {
+    if let Expr::MethodCall(call) = expr {
+        if call.method == "len" && call.args.is_empty() {
+            return Some(&call.receiver);
+        }
+    }
+    None
+}
+
+fn int_literal(expr: &Expr) -> Option<u64> {
+    if let Expr::Lit(ExprLit { lit: Lit::Int(int), .. }) = expr {
+        return int.base10_parse::<u64>().ok();
+    }
+    None
+}
+
+/// Flags the negative-`len()` anti-pattern (`x.len() == 0`, `!= 0`, `> 0`,
+/// `< 1`) and returns the matched pattern text plus its `is_empty()`
+/// rewrite, for a `x.len() OP n` binary expression. Also catches the
+/// operands reversed (`0 == x.len()`, `0 < x.len()`, `1 > x.len()`), since
+/// either ordering is equally common in the wild.
+fn len_comparison_rewrite(bin: &syn::ExprBinary) -> Option<(String, String)> {
+    if let Some(recv) = len_call_receiver(&bin.left) {
+        let n = int_literal(&bin.right)?;
+        return len_comparison_suggestion(&describe_expr(recv), &bin.op, n, false);
+    }
+    if let Some(recv) = len_call_receiver(&bin.right) {
+        let n = int_literal(&bin.left)?;
+        return len_comparison_suggestion(&describe_expr(recv), &bin.op, n, true);
+    }
+    None
+}
+
+/// Builds the matched pattern text and `is_empty()` suggestion for
+/// [`len_comparison_rewrite`]. `reversed` is true when the literal appeared
+/// on the left (`n OP x.len()`), which flips which operators mean "empty"
+/// vs. "non-empty".
+fn len_comparison_suggestion(
+    recv_desc: &str,
+    op: &BinOp,
+    n: u64,
+    reversed: bool,
+) -> Option<(String, String)> {
+    let (op_str, suggestion) = match (op, n, reversed) {
+        (BinOp::Eq(_), 0, _) => ("==", format!("{recv_desc}.is_empty()")),
+        (BinOp::Ne(_), 0, _) => ("!=", format!("!{recv_desc}.is_empty()")),
+        (BinOp::Gt(_), 0, false) => (">", format!("!{recv_desc}.is_empty()")),
+        (BinOp::Lt(_), 0, true) => ("<", format!("!{recv_desc}.is_empty()")),
+        (BinOp::Lt(_), 1, false) => ("<", format!("{recv_desc}.is_empty()")),
+        (BinOp::Gt(_), 1, true) => (">", format!("{recv_desc}.is_empty()")),
+        _ => return None,
+    };
+
+    let pattern = if reversed {
+        format!("{n} {op_str} {recv_desc}.len()")
+    } else {
+        format!("{recv_desc}.len() {op_str} {n}")
+    };
+
+    Some((pattern, suggestion))
 }
 
 struct MetricsVisitor<'a> {
     file_path: &'a str,
     metrics: &'a mut Metrics,
+    /// Name of the function/method whose body is currently being walked, so
+    /// calls found by `visit_expr` can be attributed to their caller.
+    current_fn: Option<String>,
+}
+
+impl MetricsVisitor<'_> {
+    /// Resolves the bare identifier a call expression targets, best-effort:
+    /// `foo()` / `module::foo()` resolve to `foo`, anything else (closures,
+    /// function pointers held in a field, ...) is skipped.
+    fn call_callee(expr: &Expr) -> Option<String> {
+        match expr {
+            Expr::Call(call) => match &*call.func {
+                Expr::Path(p) => p.path.segments.last().map(|s| s.ident.to_string()),
+                _ => None,
+            },
+            Expr::MethodCall(call) => Some(call.method.to_string()),
+            _ => None,
+        }
+    }
 }
 
 impl<'ast> Visit<'ast> for MetricsVisitor<'_> {
+    fn visit_expr(&mut self, node: &'ast Expr) {
+        if let Expr::Binary(bin) = node {
+            if let Some((pattern, suggestion)) = len_comparison_rewrite(bin) {
+                let line = bin.span().start().line;
+                self.metrics.lint_findings.push(LintFinding {
+                    file: self.file_path.to_string(),
+                    line,
+                    pattern,
+                    suggestion,
+                });
+            }
+        }
+
+        if let Some(caller) = &self.current_fn {
+            if let Some(callee) = Self::call_callee(node) {
+                self.metrics.call_edges.push((caller.clone(), callee));
+            }
+        }
+
+        syn::visit::visit_expr(self, node);
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        let previous = self.current_fn.replace(node.sig.ident.to_string());
+        syn::visit::visit_impl_item_fn(self, node);
+        self.current_fn = previous;
+    }
+
     fn visit_item_impl(&mut self, node: &'ast ItemImpl) {
         let type_name = if let syn::Type::Path(p) = &*node.self_ty {
             p.path
@@ -60,6 +270,7 @@ impl<'ast> Visit<'ast> for MetricsVisitor<'_> {
                         file: self.file_path.to_string(),
                         line: start.line,
                         lines,
+                        complexity: cyclomatic_complexity(&method.block),
                     },
                 );
             }
@@ -80,9 +291,12 @@ impl<'ast> Visit<'ast> for MetricsVisitor<'_> {
             line: start.line,
             lines,
             params,
+            complexity: cyclomatic_complexity(&node.block),
         });
 
+        let previous = self.current_fn.replace(node.sig.ident.to_string());
         syn::visit::visit_item_fn(self, node);
+        self.current_fn = previous;
     }
 }
 
@@ -93,6 +307,7 @@ fn analyze_file(path: &Path, metrics: &mut Metrics) -> Result<(), Box<dyn std::e
     let mut visitor = MetricsVisitor {
         file_path: &file_path,
         metrics,
+        current_fn: None,
     };
     visitor.visit_file(&syntax);
     Ok(())
@@ -134,6 +349,103 @@ fn print_table(headers: &[&str], rows: &[Vec<String>]) {
     }
 }
 
+/// Builds a distinct caller -> callees adjacency map from the raw
+/// `(caller, callee)` edges gathered while visiting function bodies.
+fn build_call_graph(edges: &[(String, String)]) -> HashMap<String, HashSet<String>> {
+    let mut graph: HashMap<String, HashSet<String>> = HashMap::new();
+    for (caller, callee) in edges {
+        graph.entry(caller.clone()).or_default().insert(callee.clone());
+    }
+    graph
+}
+
+/// The set of distinct callers per callee; each set's size is the callee's
+/// fan-in, over the same edges `build_call_graph` uses for fan-out.
+fn fan_in_counts(edges: &[(String, String)]) -> HashMap<String, HashSet<String>> {
+    let mut callers: HashMap<String, HashSet<String>> = HashMap::new();
+    for (caller, callee) in edges {
+        callers.entry(callee.clone()).or_default().insert(caller.clone());
+    }
+    callers
+}
+
+/// Tarjan's strongly-connected-components algorithm over the call graph,
+/// used to flag mutual-recursion groups. `graph` need not contain an entry
+/// for every node reachable only as a callee; `find_cycles` seeds those too.
+#[derive(Default)]
+struct Tarjan {
+    index_counter: usize,
+    indices: HashMap<String, usize>,
+    lowlink: HashMap<String, usize>,
+    on_stack: HashSet<String>,
+    stack: Vec<String>,
+    sccs: Vec<Vec<String>>,
+}
+
+impl Tarjan {
+    fn strongconnect(&mut self, node: &str, graph: &HashMap<String, HashSet<String>>) {
+        self.indices.insert(node.to_string(), self.index_counter);
+        self.lowlink.insert(node.to_string(), self.index_counter);
+        self.index_counter += 1;
+        self.stack.push(node.to_string());
+        self.on_stack.insert(node.to_string());
+
+        if let Some(callees) = graph.get(node) {
+            for callee in callees {
+                if !self.indices.contains_key(callee) {
+                    self.strongconnect(callee, graph);
+                    let callee_lowlink = self.lowlink[callee];
+                    let node_lowlink = self.lowlink[node];
+                    self.lowlink.insert(node.to_string(), node_lowlink.min(callee_lowlink));
+                } else if self.on_stack.contains(callee) {
+                    let callee_index = self.indices[callee];
+                    let node_lowlink = self.lowlink[node];
+                    self.lowlink.insert(node.to_string(), node_lowlink.min(callee_index));
+                }
+            }
+        }
+
+        if self.lowlink[node] == self.indices[node] {
+            let mut scc = Vec::new();
+            loop {
+                let member = self.stack.pop().expect("node's own SCC is still on the stack");
+                self.on_stack.remove(&member);
+                let is_node = member == node;
+                scc.push(member);
+                if is_node {
+                    break;
+                }
+            }
+            self.sccs.push(scc);
+        }
+    }
+}
+
+/// Runs Tarjan's algorithm over `graph` and returns every strongly connected
+/// component that represents real mutual recursion: more than one node, or
+/// a single node that calls itself directly.
+fn find_cycles(graph: &HashMap<String, HashSet<String>>) -> Vec<Vec<String>> {
+    let mut nodes: HashSet<&String> = graph.keys().collect();
+    for callees in graph.values() {
+        nodes.extend(callees.iter());
+    }
+
+    let mut tarjan = Tarjan::default();
+    for node in nodes {
+        if !tarjan.indices.contains_key(node) {
+            tarjan.strongconnect(node, graph);
+        }
+    }
+
+    tarjan
+        .sccs
+        .into_iter()
+        .filter(|scc| {
+            scc.len() > 1 || graph.get(&scc[0]).is_some_and(|callees| callees.contains(&scc[0]))
+        })
+        .collect()
+}
+
 fn main() {
     let src_dir = std::env::args().nth(1).unwrap_or_else(|| "src".into());
     let mut metrics = Metrics::default();
@@ -216,10 +528,108 @@ fn main() {
         print_table(&["Function", "Location", "Params"], &rows);
     }
 
+    // Report: Most complex functions (standalone functions and impl methods together)
+    println!("\n## Most complex functions\n");
+    let mut complexities: Vec<(String, String, usize, usize)> = metrics
+        .functions
+        .iter()
+        .map(|f| (f.name.clone(), f.file.clone(), f.line, f.complexity))
+        .chain(
+            metrics
+                .impl_methods
+                .values()
+                .flatten()
+                .map(|m| (m.name.clone(), m.file.clone(), m.line, m.complexity)),
+        )
+        .collect();
+    complexities.sort_by(|a, b| b.3.cmp(&a.3));
+    let rows: Vec<Vec<String>> = complexities
+        .iter()
+        .take(10)
+        .map(|(name, file, line, complexity)| {
+            let short = file.rsplit("src/").next().unwrap_or(file);
+            vec![name.clone(), format!("{}:{}", short, line), complexity.to_string()]
+        })
+        .collect();
+    print_table(&["Function", "Location", "Complexity"], &rows);
+
+    let complexity_threshold = 10;
+    let complex_fns: Vec<_> = complexities
+        .iter()
+        .filter(|(_, _, _, c)| *c > complexity_threshold)
+        .collect();
+    if !complex_fns.is_empty() {
+        println!(
+            "\n**Warning:** {} functions have cyclomatic complexity >{}\n",
+            complex_fns.len(),
+            complexity_threshold
+        );
+    }
+
+    // Report: len() comparison anti-patterns
+    println!("\n## Lint findings\n");
+    let rows: Vec<Vec<String>> = metrics
+        .lint_findings
+        .iter()
+        .map(|f| {
+            let short = f.file.rsplit("src/").next().unwrap_or(&f.file);
+            vec![
+                format!("{}:{}", short, f.line),
+                f.pattern.clone(),
+                f.suggestion.clone(),
+            ]
+        })
+        .collect();
+    if rows.is_empty() {
+        println!("No `len()` comparison anti-patterns found.");
+    } else {
+        print_table(&["Location", "Found", "Suggested"], &rows);
+    }
+
+    // Report: call-graph coupling (fan-in / fan-out)
+    let call_graph = build_call_graph(&metrics.call_edges);
+    let fan_in = fan_in_counts(&metrics.call_edges);
+
+    println!("\n## Highest fan-in\n");
+    let mut by_fan_in: Vec<_> =
+        fan_in.iter().map(|(name, callers)| (name, callers.len())).collect();
+    by_fan_in.sort_by(|a, b| b.1.cmp(&a.1));
+    let rows: Vec<Vec<String>> = by_fan_in
+        .iter()
+        .take(10)
+        .map(|(name, count)| vec![name.to_string(), count.to_string()])
+        .collect();
+    print_table(&["Function", "Callers"], &rows);
+
+    println!("\n## Highest fan-out\n");
+    let mut by_fan_out: Vec<_> =
+        call_graph.iter().map(|(name, callees)| (name, callees.len())).collect();
+    by_fan_out.sort_by(|a, b| b.1.cmp(&a.1));
+    let rows: Vec<Vec<String>> = by_fan_out
+        .iter()
+        .take(10)
+        .map(|(name, count)| vec![name.to_string(), count.to_string()])
+        .collect();
+    print_table(&["Function", "Distinct callees"], &rows);
+
+    // Report: mutual recursion groups (Tarjan SCCs with more than one node,
+    // or a self-loop)
+    println!("\n## Mutual recursion groups\n");
+    let cycles = find_cycles(&call_graph);
+    if cycles.is_empty() {
+        println!("No recursion cycles found.");
+    } else {
+        for (i, scc) in cycles.iter().enumerate() {
+            println!("{}. {}", i + 1, scc.join(" -> "));
+        }
+    }
+
     // Summary
     let total_methods: usize = metrics.impl_methods.values().map(|v| v.len()).sum();
     let total_fns = metrics.functions.len();
     println!("\n---");
     println!("Total impl methods: {}", total_methods);
     println!("Total standalone functions: {}", total_fns);
+    println!("Lint findings: {}", metrics.lint_findings.len());
+    println!("Mutual recursion groups: {}", cycles.len());
 }